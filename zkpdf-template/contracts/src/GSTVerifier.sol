@@ -3,14 +3,55 @@ pragma solidity ^0.8.20;
 
 import {ISP1Verifier} from "@sp1-contracts/ISP1Verifier.sol";
 
+/// @dev `proven_at` is the unix time the prover says it generated the proof. The prover
+///      supplies it as an input and the program cannot check it, so it is attested by the
+///      prover, not proven. Freshness policies built on it trust whoever ran the prover.
 struct PublicValuesStruct {
+    uint16 schema_version;
+    uint8 doc_kind;
     string gst_number;
     string legal_name;
+    string trade_name;
+    uint64 registration_timestamp;
+    string registration_type;
     bool signature_valid;
+    bytes32 field_commitment;
     bytes32 document_commitment;
+    bool committed_match;
     bytes32 public_key_hash;
+    uint64 proven_at;
 }
 
+/// @notice Fixed-size GST public values; the legal name is only bound by a salted commitment.
+/// @dev `legal_name_commitment` is keccak256(abi.encodePacked(salt, uint32(18),
+///      "zkverifid:field:v1", uint32(10), "legal_name", uint32(bytes(name).length), name)),
+///      where `salt` is the prover's private 32-byte salt. Checking a name against it needs
+///      the salt from the holder; without it the name cannot be brute forced.
+///      `proven_at` is prover-attested, as in `PublicValuesStruct`.
+struct PackedPublicValuesStruct {
+    uint16 schema_version;
+    uint8 doc_kind;
+    bytes15 gst_number;
+    uint8 state_code;
+    bytes32 legal_name_commitment;
+    uint64 registration_timestamp;
+    bool signature_valid;
+    bytes32 field_commitment;
+    bytes32 document_commitment;
+    bool committed_match;
+    bytes32 public_key_hash;
+    uint64 proven_at;
+}
+
+/// @notice Public values layout this contract decodes; the program bumps it on any layout change.
+uint16 constant SCHEMA_VERSION = 6;
+
+/// @notice `doc_kind` value committed by the program for fully revealed GST certificates.
+uint8 constant DOC_KIND_GST = 0;
+
+/// @notice `doc_kind` value committed by the program for packed GST public values.
+uint8 constant DOC_KIND_GST_PACKED = 7;
+
 /// @title GSTVerifier.
 /// @author Succinct Labs
 /// @notice This contract implements GST certificate verification using SP1 zkVM proofs.
@@ -54,6 +95,8 @@ contract GSTVerifier {
     {
         ISP1Verifier(verifier).verifyProof(gstProgramVKey, _publicValues, _proofBytes);
         PublicValuesStruct memory publicValues = abi.decode(_publicValues, (PublicValuesStruct));
+        require(publicValues.schema_version == SCHEMA_VERSION, "GSTVerifier: unsupported schema");
+        require(publicValues.doc_kind == DOC_KIND_GST, "GSTVerifier: not a GST proof");
         return (
             publicValues.gst_number,
             publicValues.legal_name,
@@ -63,6 +106,29 @@ contract GSTVerifier {
         );
     }
 
+    /// @notice Verifies a proof committed with the packed GST layout, which decodes without
+    ///         dynamic offsets and costs less calldata than `verifyGSTProof`.
+    /// @param _publicValues The encoded public values.
+    /// @param _proofBytes The encoded proof.
+    function verifyPackedGSTProof(bytes calldata _publicValues, bytes calldata _proofBytes)
+        public
+        view
+        returns (bytes15, bytes32, bool, bytes32, bytes32)
+    {
+        ISP1Verifier(verifier).verifyProof(gstProgramVKey, _publicValues, _proofBytes);
+        PackedPublicValuesStruct memory publicValues =
+            abi.decode(_publicValues, (PackedPublicValuesStruct));
+        require(publicValues.schema_version == SCHEMA_VERSION, "GSTVerifier: unsupported schema");
+        require(publicValues.doc_kind == DOC_KIND_GST_PACKED, "GSTVerifier: not a packed GST proof");
+        return (
+            publicValues.gst_number,
+            publicValues.legal_name_commitment,
+            publicValues.signature_valid,
+            publicValues.document_commitment,
+            publicValues.public_key_hash
+        );
+    }
+
     /// @notice Verify GST certificate and store the verification result
     /// @param _publicValues The encoded public values.
     /// @param _proofBytes The encoded proof.
@@ -72,6 +138,8 @@ contract GSTVerifier {
     {
         ISP1Verifier(verifier).verifyProof(gstProgramVKey, _publicValues, _proofBytes);
         PublicValuesStruct memory publicValues = abi.decode(_publicValues, (PublicValuesStruct));
+        require(publicValues.schema_version == SCHEMA_VERSION, "GSTVerifier: unsupported schema");
+        require(publicValues.doc_kind == DOC_KIND_GST, "GSTVerifier: not a GST proof");
         
         // Store verification results
         verifiedCertificates[publicValues.document_commitment] = true;
@@ -0,0 +1,37 @@
+//! Off-chain timings of the verification and extraction path the zkVM program runs.
+//!
+//! ```shell
+//! cargo bench -p zkpdf-template-lib
+//! ```
+//! Wall-clock time here tracks the cycle count only loosely; the committed cycle budget is
+//! checked by `script/tests/cycle_budget.rs`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zkpdf_template_lib::{
+    extract::{extract_gstin, extract_pan},
+    verify_any_document,
+};
+
+const SAMPLE_GST_PDF: &[u8] = include_bytes!("../../samples/GST-certificate.pdf");
+
+const GST_TEXT: &str = "GSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS\nTrade Name ACME\n\
+                        Date of Liability 01/07/2017\n";
+
+const PAN_TEXT: &str = "INCOME TAX DEPARTMENT\nName\nRAVI KUMAR\nFather's Name\nSURESH KUMAR\n\
+                        Date of Birth\n01/01/1990\nPermanent Account Number\nABCPK1234F\n";
+
+fn verification(c: &mut Criterion) {
+    c.bench_function("verify_any_document/gst_sample", |b| {
+        b.iter(|| verify_any_document(black_box(SAMPLE_GST_PDF.to_vec())))
+    });
+}
+
+fn extraction(c: &mut Criterion) {
+    c.bench_function("extract_gstin", |b| {
+        b.iter(|| extract_gstin(black_box(GST_TEXT)))
+    });
+    c.bench_function("extract_pan", |b| b.iter(|| extract_pan(black_box(PAN_TEXT))));
+}
+
+criterion_group!(benches, verification, extraction);
+criterion_main!(benches);
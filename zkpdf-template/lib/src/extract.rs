@@ -0,0 +1,97 @@
+//! Identifier parsing on plain text
+//!
+//! For callers that already have the document text, e.g. from their own OCR, and only
+//! need the identifiers. The PDF verifiers use the same functions, so the patterns and
+//! validation rules live in one place.
+
+use regex::{Captures, Regex};
+
+use crate::utils::{
+    extract_all_gstins, pan_holder_category, validate_gstin_checksum, AADHAAR_DOB_REGEX,
+    AADHAAR_MASKED_UID_REGEX, GST_NUMBER_CONFUSABLE_REGEX, PAN_DOB_REGEX, PAN_NUMBER_REGEX,
+};
+
+/// The first GSTIN in the text whose checksum is valid
+pub fn extract_gstin(text: &str) -> Option<String> {
+    extract_all_gstins(text)
+        .into_iter()
+        .find(|gstin| validate_gstin_checksum(gstin))
+}
+
+/// The first PAN in the text whose 4th character is a known holder category
+pub fn extract_pan(text: &str) -> Option<String> {
+    pan_candidates(text)
+        .find(|pan| pan_holder_category(pan).is_some())
+        .map(str::to_string)
+}
+
+/// Every substring shaped like a PAN, in order of appearance
+pub(crate) fn pan_candidates(text: &str) -> impl Iterator<Item = &str> {
+    PAN_NUMBER_REGEX
+        .captures_iter(text)
+        .filter_map(|cap| cap.get(1))
+        .map(|m| m.as_str())
+}
+
+/// Characters `redact_sensitive` leaves visible at the end of each identifier
+pub const DEFAULT_REDACTION_SUFFIX: usize = 4;
+
+/// `text` with every GSTIN, PAN, masked Aadhaar number and date of birth masked down to
+/// its last four characters, for logging the text extraction actually saw
+pub fn redact_sensitive(text: &str) -> String {
+    redact_sensitive_with(text, DEFAULT_REDACTION_SUFFIX)
+}
+
+/// Like `redact_sensitive`, leaving the last `visible_suffix` letters and digits of each
+/// match. Separators such as the `/` in a date are kept in place.
+///
+/// GSTINs are redacted before PANs, since every GSTIN contains one, and GSTIN-shaped
+/// strings are redacted even when their checksum or state code is wrong.
+pub fn redact_sensitive_with(text: &str, visible_suffix: usize) -> String {
+    // (pattern, capture group holding the value)
+    let patterns: [(&Regex, usize); 5] = [
+        (&GST_NUMBER_CONFUSABLE_REGEX, 1),
+        (&PAN_NUMBER_REGEX, 1),
+        (&AADHAAR_MASKED_UID_REGEX, 0),
+        (&AADHAAR_DOB_REGEX, 1),
+        (&PAN_DOB_REGEX, 1),
+    ];
+
+    let mut redacted = text.to_string();
+    for (pattern, group) in patterns {
+        redacted = pattern
+            .replace_all(&redacted, |cap: &Captures| {
+                let whole = cap.get(0).unwrap();
+                let value = cap.get(group).unwrap_or(whole);
+                let (start, end) = (value.start() - whole.start(), value.end() - whole.start());
+                format!(
+                    "{}{}{}",
+                    &whole.as_str()[..start],
+                    mask_alphanumerics(value.as_str(), visible_suffix),
+                    &whole.as_str()[end..]
+                )
+            })
+            .into_owned();
+    }
+    redacted
+}
+
+/// Replaces every letter and digit but the last `visible_suffix` with `*`
+fn mask_alphanumerics(value: &str, visible_suffix: usize) -> String {
+    let mut to_mask = value
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .count()
+        .saturating_sub(visible_suffix);
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() && to_mask > 0 {
+                to_mask -= 1;
+                '*'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
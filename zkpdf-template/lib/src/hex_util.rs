@@ -0,0 +1,75 @@
+//! `0x`-prefixed hex encoding for fixtures, CLI arguments and JSON output
+//!
+//! Every hex string the scripts print or parse goes through these two functions, so a
+//! change of format only has to be made here.
+
+use std::error::Error;
+use std::fmt;
+
+/// Why a string is not valid hex
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexError {
+    OddLength,
+    InvalidCharacter { character: char, index: usize },
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HexError::OddLength => write!(f, "Hex string has an odd number of digits"),
+            HexError::InvalidCharacter { character, index } => {
+                write!(
+                    f,
+                    "Invalid hex character {:?} at position {}",
+                    character, index
+                )
+            }
+        }
+    }
+}
+
+impl Error for HexError {}
+
+/// Lowercase hex with a `0x` prefix
+pub fn to_0x(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Decodes hex with or without a single `0x` prefix
+pub fn from_0x(s: &str) -> Result<Vec<u8>, HexError> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    hex::decode(digits).map_err(|err| match err {
+        hex::FromHexError::InvalidHexCharacter { c, index } => HexError::InvalidCharacter {
+            character: c,
+            // Position in `s`, counting the prefix
+            index: index + (s.len() - digits.len()),
+        },
+        hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
+            HexError::OddLength
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trips_with_prefix() {
+        let bytes = [0x00, 0xab, 0xff];
+        assert_eq!(to_0x(&bytes), "0x00abff");
+        assert_eq!(from_0x(&to_0x(&bytes)).unwrap(), bytes);
+        assert_eq!(from_0x("00ABFF").unwrap(), bytes);
+        assert_eq!(from_0x("0x").unwrap(), Vec::<u8>::new());
+
+        assert_eq!(from_0x("0xabc"), Err(HexError::OddLength));
+        assert_eq!(
+            from_0x("0x0g"),
+            Err(HexError::InvalidCharacter {
+                character: 'g',
+                index: 3
+            })
+        );
+        assert!(from_0x("0x0x00").is_err());
+    }
+}
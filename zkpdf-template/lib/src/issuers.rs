@@ -0,0 +1,305 @@
+//! Issuer CA certificates pinned into the binary
+//!
+//! For air-gapped deployments that cannot fetch the issuer's chain, the signer
+//! certificate carried in a PDF signature is checked against CA certificates compiled in
+//! here. The check is plain Rust with no I/O, so it runs inside the zkVM as well.
+//!
+//! Only RSA PKCS#1 v1.5 signatures over SHA-256, SHA-384 or SHA-512 are verified, which
+//! covers the Indian CAs that issue document signer certificates.
+//!
+//! # Updating pinned issuers
+//!
+//! Issuers rotate their document signer CA every few years, e.g. "Capricorn Sub CA for
+//! Document Signer DSC 2022" for the GSTN signer of `samples/GST-certificate.pdf`:
+//!
+//! 1. Download the new CA certificate from the CA's repository, listed by the Controller
+//!    of Certifying Authorities, and check its fingerprint against the published one.
+//! 2. Save it as PEM under `lib/issuers/` and add it to `PINNED_ISSUER_PEMS` with
+//!    `include_bytes!`.
+//! 3. Keep the previous CA while PDFs it signed are still in use; remove it only once
+//!    they need not verify any more.
+//! 4. Rebuild the program: the ELF, and so the verification key, changes, so update the
+//!    vkey used by on-chain verifiers.
+
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::error::Error;
+use std::fmt;
+
+use crate::utils::{read_der, tbs_fields_after_serial};
+
+/// PEM CA certificates a signer certificate may chain to.
+///
+/// Empty until an issuer CA certificate is added, see the module documentation; until
+/// then `verify_signer_chain` fails with `ChainError::NoPinnedIssuer`. The GSTN sample's
+/// issuer, "Capricorn Sub CA for Document Signer DSC 2022", is the first to add: the PDF
+/// embeds only the signer certificate, so the CA must be fetched from Capricorn's
+/// repository and its fingerprint checked as in step 1.
+pub const PINNED_ISSUER_PEMS: &[&[u8]] = &[];
+
+const OID_SHA256_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b];
+const OID_SHA384_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+const OID_SHA512_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d];
+
+/// DER `DigestInfo` prefixes preceding the hash in a PKCS#1 v1.5 signature
+const SHA256_DIGEST_INFO: &[u8] = &[
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+const SHA384_DIGEST_INFO: &[u8] = &[
+    0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05,
+    0x00, 0x04, 0x30,
+];
+const SHA512_DIGEST_INFO: &[u8] = &[
+    0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05,
+    0x00, 0x04, 0x40,
+];
+
+/// Why the signer certificate does not chain to a pinned issuer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// No pinned CA has the subject named as the signer certificate's issuer
+    NoPinnedIssuer,
+    /// The signer certificate could not be parsed
+    MalformedCertificate,
+    /// The signer certificate is signed with an algorithm other than RSA with SHA-2
+    UnsupportedAlgorithm,
+    /// A pinned CA matched by name, but its key did not sign the signer certificate
+    SignatureMismatch,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChainError::NoPinnedIssuer => {
+                write!(f, "Signer certificate issuer is not a pinned CA")
+            }
+            ChainError::MalformedCertificate => write!(f, "Certificate could not be parsed"),
+            ChainError::UnsupportedAlgorithm => {
+                write!(f, "Signer certificate signature algorithm is not supported")
+            }
+            ChainError::SignatureMismatch => {
+                write!(f, "Signer certificate was not signed by the pinned CA")
+            }
+        }
+    }
+}
+
+impl Error for ChainError {}
+
+/// Checks that the DER signer certificate, from `utils::signer_certificate`, was issued
+/// by one of `PINNED_ISSUER_PEMS`
+pub fn verify_signer_chain(certificate: &[u8]) -> Result<(), ChainError> {
+    verify_signer_chain_with(certificate, PINNED_ISSUER_PEMS)
+}
+
+/// Like `verify_signer_chain`, against the given PEM CA certificates. PEMs that do not
+/// parse are skipped, so one bad pin cannot fail every check.
+pub fn verify_signer_chain_with(
+    certificate: &[u8],
+    issuer_pems: &[&[u8]],
+) -> Result<(), ChainError> {
+    let signer = SignedCertificate::parse(certificate).ok_or(ChainError::MalformedCertificate)?;
+
+    let mut result = Err(ChainError::NoPinnedIssuer);
+    for pem in issuer_pems {
+        let Some(der) = pem_to_der(pem) else {
+            continue;
+        };
+        let Some(issuer) = SignedCertificate::parse(&der) else {
+            continue;
+        };
+        if issuer.subject != signer.issuer {
+            continue;
+        }
+        // CAs that re-key keep their name, so try every pinned CA with a matching subject
+        result = signer.verify_signed_by(issuer.public_key);
+        if result.is_ok() {
+            break;
+        }
+    }
+    result
+}
+
+/// Whether the DER certificate's signature verifies under its own public key, whatever
+/// issuer it names; `None` when it does not parse or its algorithm is not supported
+pub fn signed_by_own_key(certificate: &[u8]) -> Option<bool> {
+    let certificate = SignedCertificate::parse(certificate)?;
+    match certificate.verify_signed_by(certificate.public_key) {
+        Ok(()) => Some(true),
+        Err(ChainError::SignatureMismatch) => Some(false),
+        Err(_) => None,
+    }
+}
+
+/// The parts of a DER X.509 certificate needed to check who signed it
+struct SignedCertificate<'a> {
+    /// Full DER TBSCertificate, the signed bytes
+    tbs: &'a [u8],
+    /// Full DER issuer and subject names, compared byte for byte
+    issuer: &'a [u8],
+    subject: &'a [u8],
+    /// `SubjectPublicKeyInfo` contents
+    public_key: &'a [u8],
+    signature_algorithm: &'a [u8],
+    signature: &'a [u8],
+}
+
+impl<'a> SignedCertificate<'a> {
+    fn parse(der: &'a [u8]) -> Option<Self> {
+        // Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signatureValue }
+        let (tag, certificate, _) = read_der(der)?;
+        if tag != 0x30 {
+            return None;
+        }
+        let tbs = der_element(certificate)?;
+        let (_, algorithm, rest) = read_der(&certificate[tbs.len()..])?;
+        let (_, signature_algorithm, _) = read_der(algorithm)?;
+        let (tag, signature, _) = read_der(rest)?;
+        // BIT STRING with no unused bits
+        let signature = match (tag, signature.split_first()?) {
+            (0x03, (0, signature)) => signature,
+            _ => return None,
+        };
+
+        // Fields after the serial number: signature, issuer, validity, subject, spki
+        let rest = tbs_fields_after_serial(der)?;
+        let rest = read_der(rest)?.2;
+        let issuer = der_element(rest)?;
+        let rest = read_der(&rest[issuer.len()..])?.2;
+        let subject = der_element(rest)?;
+        let (_, public_key, _) = read_der(&rest[subject.len()..])?;
+
+        Some(SignedCertificate {
+            tbs,
+            issuer,
+            subject,
+            public_key,
+            signature_algorithm,
+            signature,
+        })
+    }
+
+    /// Verifies the RSA PKCS#1 v1.5 signature over the TBSCertificate with the issuer's
+    /// `SubjectPublicKeyInfo` contents
+    fn verify_signed_by(&self, issuer_public_key: &[u8]) -> Result<(), ChainError> {
+        let (digest_info, hash) = match self.signature_algorithm {
+            OID_SHA256_WITH_RSA => (SHA256_DIGEST_INFO, Sha256::digest(self.tbs).to_vec()),
+            OID_SHA384_WITH_RSA => (SHA384_DIGEST_INFO, Sha384::digest(self.tbs).to_vec()),
+            OID_SHA512_WITH_RSA => (SHA512_DIGEST_INFO, Sha512::digest(self.tbs).to_vec()),
+            _ => return Err(ChainError::UnsupportedAlgorithm),
+        };
+        let (modulus, exponent) =
+            rsa_public_key(issuer_public_key).ok_or(ChainError::UnsupportedAlgorithm)?;
+
+        let modulus_len = modulus.len();
+        let n = BigUint::from_bytes_be(modulus);
+        let s = BigUint::from_bytes_be(self.signature);
+        if self.signature.len() != modulus_len || s >= n {
+            return Err(ChainError::SignatureMismatch);
+        }
+        let recovered = s
+            .modpow(&BigUint::from_bytes_be(exponent), &n)
+            .to_bytes_be();
+
+        // EM = 0x00 || 0x01 || 0xff.. || 0x00 || DigestInfo || hash, left-aligned to the
+        // modulus length
+        let padding_len = modulus_len
+            .checked_sub(3 + digest_info.len() + hash.len())
+            .filter(|&len| len >= 8)
+            .ok_or(ChainError::SignatureMismatch)?;
+        let mut expected = vec![0x01];
+        expected.resize(1 + padding_len, 0xff);
+        expected.push(0x00);
+        expected.extend_from_slice(digest_info);
+        expected.extend_from_slice(&hash);
+
+        // `to_bytes_be` drops the leading 0x00
+        if recovered == expected {
+            Ok(())
+        } else {
+            Err(ChainError::SignatureMismatch)
+        }
+    }
+}
+
+/// The complete DER element, tag and length included, at the start of `input`
+fn der_element(input: &[u8]) -> Option<&[u8]> {
+    let (_, _, rest) = read_der(input)?;
+    Some(&input[..input.len() - rest.len()])
+}
+
+/// RSA (modulus, exponent) with leading zero bytes stripped, from `SubjectPublicKeyInfo`
+/// contents
+fn rsa_public_key(spki: &[u8]) -> Option<(&[u8], &[u8])> {
+    // SubjectPublicKeyInfo ::= SEQUENCE { algorithm, subjectPublicKey BIT STRING }
+    let (_, _, rest) = read_der(spki)?;
+    let (tag, key, _) = read_der(rest)?;
+    let key = match (tag, key.split_first()?) {
+        (0x03, (0, key)) => key,
+        _ => return None,
+    };
+
+    // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+    let (tag, key, _) = read_der(key)?;
+    if tag != 0x30 {
+        return None;
+    }
+    let (modulus_tag, modulus, rest) = read_der(key)?;
+    let (exponent_tag, exponent, _) = read_der(rest)?;
+    if modulus_tag != 0x02 || exponent_tag != 0x02 {
+        return None;
+    }
+    Some((strip_leading_zeros(modulus), strip_leading_zeros(exponent)))
+}
+
+fn strip_leading_zeros(integer: &[u8]) -> &[u8] {
+    let start = integer
+        .iter()
+        .position(|b| *b != 0)
+        .unwrap_or(integer.len());
+    &integer[start..]
+}
+
+/// DER bytes of the first certificate in a PEM file
+pub fn pem_to_der(pem: &[u8]) -> Option<Vec<u8>> {
+    let pem = std::str::from_utf8(pem).ok()?;
+    let body = pem
+        .split("-----BEGIN CERTIFICATE-----")
+        .nth(1)?
+        .split("-----END CERTIFICATE-----")
+        .next()?;
+    base64_decode(body)
+}
+
+/// Decodes standard base64, skipping whitespace
+fn base64_decode(encoded: &str) -> Option<Vec<u8>> {
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+    let symbols: Vec<u8> = encoded
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let data = symbols
+        .strip_suffix(b"==")
+        .or_else(|| symbols.strip_suffix(b"="))
+        .unwrap_or(&symbols);
+
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &c in data {
+        buffer = (buffer << 6) | u32::from(value(c)?);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
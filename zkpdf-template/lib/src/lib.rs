@@ -3,126 +3,2407 @@
 //! This library provides functions for verifying GST certificates and extracting
 //! key information from PDF documents. It handles PDF parsing, signature verification,
 //! and data extraction with proper error handling.
+//!
+//! Extraction and commitments only need `std`, so the crate can be used off-chain without
+//! any SP1 dependency. The `zkvm` feature pulls in `sp1-zkvm` for guest-side helpers.
 
-use alloy_sol_types::sol;
+use alloy_primitives::keccak256;
+use alloy_sol_types::{sol, SolType};
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
 
-use zkpdf_lib::{verify_and_extract, PdfSignatureResult};
+use zkpdf_lib::{extract_text, verify_and_extract, PdfSignatureResult, PdfVerifiedContent};
 
-use crate::utils::{GSTVerificationError, PANVerificationError};
+use crate::extract::{extract_pan, pan_candidates};
+use crate::utils::{
+    aadhaar_generate_commitment, assert_text_within_signed_range, date_timestamp, digest_algorithm,
+    dl_generate_commitment, document_key, embedded_certificates, extract_all_gstins,
+    extract_annexure, extract_confusable_gstins, extract_special_gstins, full_document_commitment,
+    gender_code, gst_generate_commitment, gst_generate_commitment_masked, gstin_category,
+    gstin_matches_pan, gstin_state, has_extractable_text, is_encrypted_pdf, is_trusted_issuer,
+    is_uidai_signer, log_field, mask_identifier, name_variants, normalize_address,
+    normalize_bilingual_name, normalize_name, pan_generate_commitment_masked, pan_holder_category,
+    pan_names, pan_names_with, parse_business_constitution, parse_gender, parse_indian_dob,
+    parse_registration_type, salted_field_commitment, signature_key_info, signer_cert_validity,
+    signer_certificate, signer_common_name, signer_is_self_signed, truncate_scan_text,
+    validate_gstin_checksum, AadhaarVerificationError, BusinessConstitution, CommitmentError,
+    DLVerificationError, DigestAlgo, DisclosureMask, DocumentKey, DocumentVerificationError,
+    ExtractionWarning, GSTVerificationError, Gender, GstAnnexure, GstConfig, GstRegistrationType,
+    GstinCategory, KeyInfo, PANVerificationError, PanCategory, PanConfig, PublicValuesError,
+    SignedPdfError, TextSource, AADHAAR_DOB_REGEX, AADHAAR_MASKED_UID_REGEX, AADHAAR_NAME_REGEX,
+    DEFAULT_MAX_SCAN_CHARS, DL_EXPIRY_DATE_REGEX, DL_HOLDER_NAME_REGEX, DL_ISSUING_AUTHORITY_REGEX,
+    DL_LICENSE_TYPE_REGEX, DL_NUMBER_REGEX, GENDER_REGEX, GENDER_WITHHELD, GST_ADDRESS_REGEX,
+    GST_CONSTITUTION_REGEX, GST_EMAIL_REGEX, GST_LEGAL_NAME_REGEX, GST_MOBILE_LAST4_REGEX,
+    GST_NUMBER_REGEX, GST_REGISTRATION_DATE_REGEX, GST_REGISTRATION_TYPE_REGEX,
+    GST_TRADE_NAME_REGEX, MAX_PDF_BYTES, PAN_DOB_REGEX, PAN_GENDER_REGEX, PAN_NUMBER_REGEX,
+    PAN_PRINT_DATE_REGEX,
+};
 
+pub mod extract;
+pub mod hex_util;
+#[cfg(feature = "embedded-issuers")]
+pub mod issuers;
+#[cfg(feature = "ocr")]
+mod ocr;
 pub mod utils;
 
+/// Emits an SP1 cycle-tracker marker when built with the `profile` feature
+macro_rules! cycle_tracker {
+    ($marker:literal, $phase:literal) => {
+        #[cfg(feature = "profile")]
+        println!(concat!("cycle-tracker-", $marker, ": ", $phase));
+    };
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct GSTCertificate {
     pub gst_number: String,
+    pub additional_gst_numbers: Vec<String>,
     pub legal_name: String,
+    /// `name_variants` of `legal_name`, with and without its legal-form suffix, for
+    /// off-chain name matching. Only `legal_name` is committed.
+    #[serde(default)]
+    pub legal_name_variants: Vec<String>,
+    /// Annexure A and B of a REG-06 certificate, when its pages are available; not
+    /// committed
+    #[serde(default)]
+    pub annexure: Option<GstAnnexure>,
+    /// `TextSource::Ocr` when the fields were recognized from rendered pages by
+    /// `verify_gst_certificate_with_ocr` and are not bound to the signature
+    #[serde(default)]
+    pub text_source: TextSource,
+    pub trade_name: Option<String>,
+    pub registration_date: Option<NaiveDate>,
+    pub address: Option<String>,
+    pub constitution: Option<BusinessConstitution>,
+    pub registration_type: Option<GstRegistrationType>,
+    /// Contact email, read only with `CertificateVerifierBuilder::extract_contact`.
+    ///
+    /// Off-chain use only: never committed or part of the public values, and must not be
+    /// revealed on-chain without the holder's explicit consent.
+    pub email: Option<String>,
+    /// Last four digits of the contact mobile number, read only with `extract_contact`;
+    /// the full number is never read. Off-chain use only, like `email`.
+    pub mobile_last4: Option<String>,
+    pub state_code: u8,
+    pub state_name: Option<String>,
+    #[serde(with = "crate::utils::signature_serde")]
+    pub signature: PdfSignatureResult,
+    #[serde(default)]
+    pub signer: SignerInfo,
+}
+
+impl GSTCertificate {
+    /// One-line summary with the GSTIN masked to its last four characters
+    pub fn redacted(&self) -> String {
+        self.summary(&mask_identifier(&self.gst_number))
+    }
+
+    /// One-line summary with the full GSTIN, for when the raw value is explicitly wanted
+    pub fn full_display(&self) -> String {
+        self.summary(&self.gst_number)
+    }
+
+    /// Taxpayer type of the primary GSTIN, `None` only if checksum validation was off
+    /// and the number fits no known structure
+    pub fn gstin_category(&self) -> Option<GstinCategory> {
+        gstin_category(&self.gst_number)
+    }
+
+    fn summary(&self, gst_number: &str) -> String {
+        format!(
+            "GSTIN {} | {} | {} | signature {}",
+            gst_number,
+            self.legal_name,
+            self.state_name.as_deref().unwrap_or("unknown state"),
+            if self.signature.is_valid {
+                "valid"
+            } else {
+                "invalid"
+            }
+        )
+    }
+}
+
+/// Redacted by default so GSTINs don't leak into logs
+impl fmt::Display for GSTCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.redacted())
+    }
+}
+
+impl fmt::Debug for GSTCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GSTCertificate")
+            .field("gst_number", &mask_identifier(&self.gst_number))
+            .field("legal_name", &self.legal_name)
+            .field("trade_name", &self.trade_name)
+            .field("registration_date", &self.registration_date)
+            .field("constitution", &self.constitution)
+            .field("state_code", &self.state_code)
+            .field("signature_valid", &self.signature.is_valid)
+            .field("issuer", &self.signer.issuer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Equality is document identity, not field-by-field equality: two certificates are equal
+/// when they commit identically, so parses of one signed document collapse in a `HashSet`
+/// even if fields outside the commitment, such as the annexure, differ. Certificates that
+/// cannot be committed because their signature is invalid or keyless are compared by
+/// GSTIN and signed digest instead. An OCR parse never equals a text-layer parse of the
+/// same PDF, since only the latter's fields are bound to the signature.
+impl PartialEq for GSTCertificate {
+    fn eq(&self, other: &Self) -> bool {
+        self.document_key() == other.document_key() && self.text_source == other.text_source
+    }
+}
+
+impl Eq for GSTCertificate {}
+
+/// Hashes the document identity, consistent with `PartialEq`
+impl Hash for GSTCertificate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.document_key().hash(state);
+        self.text_source.hash(state);
+    }
+}
+
+impl GSTCertificate {
+    fn document_key(&self) -> DocumentKey<'_> {
+        document_key(self, &self.gst_number, &self.signature.message_digest)
+    }
+}
+
+/// e-Aadhaar holder details. Only the last four digits of the Aadhaar number are read.
+#[derive(Serialize, Deserialize)]
+pub struct AadhaarCertificate {
+    pub masked_uid_last4: String,
+    pub name: String,
+    pub dob: String,
+    pub gender: Option<Gender>,
+    #[serde(with = "crate::utils::signature_serde")]
     pub signature: PdfSignatureResult,
+    #[serde(default)]
+    pub signer: SignerInfo,
 }
 
+/// Version of the public values layout committed by the program
+pub const PUBLIC_VALUES_SCHEMA_VERSION: u16 = 6;
+
 sol! {
     /// The public values encoded as a struct that can be easily deserialized inside Solidity.
+    ///
+    /// `schema_version` is bumped whenever a field is added, removed or reordered in any
+    /// public values struct, so on-chain verifiers should require the version they decode.
+    ///
+    /// `field_commitment` binds the salted extracted fields; `document_commitment` binds the
+    /// full page text, so distinct PDFs with the same fields commit differently.
+    ///
+    /// `public_key_hash` is the keccak256 of the key of the PDF's only signer; PDFs with
+    /// more than one signature are rejected before extraction.
+    ///
+    /// `proven_at` is the unix time the prover says it generated the proof. The prover
+    /// supplies it as an input, so it is attested by whoever ran the prover rather than
+    /// proven: a verifier applying a freshness policy must trust that prover's clock.
     struct GSTValuesStruct {
+        uint16 schema_version;
+        uint8 doc_kind;
+        string gst_number;
+        string legal_name;
+        string trade_name;
+        uint64 registration_timestamp;
+        string registration_type;
+        bool signature_valid;
+        bytes32 field_commitment;
+        bytes32 document_commitment;
+        bool committed_match;
+        bytes32 public_key_hash;
+        uint64 proven_at;
+    }
+
+    /// GST public values with selective disclosure. Hidden fields are left empty and are
+    /// only bound through their commitment, e.g. `gst_number_commitment` is
+    /// `utils::salted_field_commitment(salt, "gst_number", gst_number)` and
+    /// `legal_name_commitment` the same over `"legal_name"`. Without the salt they cannot
+    /// be brute forced, and proofs of one holder under different salts do not link.
+    struct GSTValuesStructPrivate {
+        uint16 schema_version;
+        uint8 doc_kind;
         string gst_number;
+        bytes32 gst_number_commitment;
         string legal_name;
+        bytes32 legal_name_commitment;
+        bool signature_valid;
+        bytes32 field_commitment;
+        bytes32 document_commitment;
+        bool committed_match;
+        bytes32 public_key_hash;
+        uint64 proven_at;
+    }
+
+    /// GST public values with only fixed-size fields, for on-chain verifiers.
+    ///
+    /// Every field is static, so the struct ABI-encodes to 384 bytes with no offsets or
+    /// length words, against roughly 700 bytes for `GSTValuesStruct`. That saves calldata
+    /// gas and lets Solidity read fields without dynamic decoding, at the cost of the
+    /// cleartext legal name and trade name: the name is bound only through
+    /// `legal_name_commitment`, `utils::salted_field_commitment(salt, "legal_name", name)`,
+    /// so a verifier must already know the name and the salt to check it.
+    /// `gst_number` is the 15 ASCII bytes of the GSTIN, or zero when the mask hides it.
+    struct GSTValuesPackedStruct {
+        uint16 schema_version;
+        uint8 doc_kind;
+        bytes15 gst_number;
+        uint8 state_code;
+        bytes32 legal_name_commitment;
+        uint64 registration_timestamp;
         bool signature_valid;
+        bytes32 field_commitment;
         bytes32 document_commitment;
+        bool committed_match;
         bytes32 public_key_hash;
+        uint64 proven_at;
     }
 
+    /// PAN public values, versioned through `schema_version` like `GSTValuesStruct`.
+    /// `gender` is 0 when the PAN doesn't print one and 255 when the disclosure mask hides it.
     struct PANValuesStruct {
+        uint16 schema_version;
+        uint8 doc_kind;
         string pan_number;
         string legal_name;
         string dob;
+        uint64 print_timestamp;
+        uint8 gender;
+        uint32[] age_thresholds;
+        bool[] age_over_thresholds;
+        string age_as_of;
+        bool signature_valid;
+        bytes32 field_commitment;
+        bytes32 document_commitment;
+        bool committed_match;
+        bytes32 public_key_hash;
+        uint64 proven_at;
+    }
+
+    struct DLValuesStruct {
+        uint16 schema_version;
+        uint8 doc_kind;
+        string dl_number;
+        string holder_name;
+        string issuing_authority;
+        string license_type;
+        string expiry_date;
+        bool signature_valid;
+        bytes32 field_commitment;
+        bytes32 document_commitment;
+        bool committed_match;
+        bytes32 public_key_hash;
+        uint64 proven_at;
+    }
+
+    /// Aadhaar public values; `gender` is 0 when the PDF doesn't print one.
+    struct AadhaarValuesStruct {
+        uint16 schema_version;
+        uint8 doc_kind;
+        string masked_uid_last4;
+        string name;
+        string dob;
+        uint8 gender;
         bool signature_valid;
+        bytes32 field_commitment;
         bytes32 document_commitment;
+        bool committed_match;
         bytes32 public_key_hash;
+        uint64 proven_at;
+    }
+
+    /// Several documents proven together; entry `i` of each array describes the `i`th PDF
+    struct BundleValuesStruct {
+        uint16 schema_version;
+        uint8 doc_kind;
+        uint8[] doc_kinds;
+        bytes32[] field_commitments;
+        bytes32[] document_commitments;
+        bytes32[] public_key_hashes;
+        bool all_signatures_valid;
+        uint64 proven_at;
+    }
+
+    /// A single document's public values together with the signer's full public key, for
+    /// verifiers that check the key or its certificate chain themselves.
+    ///
+    /// `values` holds the document's usual public values struct, whose `public_key_hash`
+    /// is `keccak256(public_key)`. A DER certificate adds one to two kilobytes to the
+    /// public values, which the proof hashes and an on-chain verifier receives as
+    /// calldata, so the hash-only layout is the default.
+    struct PublicKeyValuesStruct {
+        uint16 schema_version;
+        uint8 doc_kind;
+        bytes values;
+        bytes public_key;
+    }
+}
+
+/// Byte range of an extracted field within the joined page text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSpan {
+    pub start: usize,
+    pub end: usize,
+    pub value: String,
+}
+
+/// A GST certificate together with where its key fields were found in the text
+pub struct GSTExtraction {
+    pub certificate: GSTCertificate,
+    pub gst_number_span: FieldSpan,
+    pub legal_name_span: FieldSpan,
+}
+
+/// Discriminator committed after `schema_version` in every public values struct
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum CertificateKind {
+    Gst = 0,
+    Pan = 1,
+    DrivingLicense = 2,
+    GstPrivate = 3,
+    Aadhaar = 4,
+    Bundle = 5,
+    WithPublicKey = 6,
+    GstPacked = 7,
+}
+
+impl CertificateKind {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(CertificateKind::Gst),
+            1 => Some(CertificateKind::Pan),
+            2 => Some(CertificateKind::DrivingLicense),
+            3 => Some(CertificateKind::GstPrivate),
+            4 => Some(CertificateKind::Aadhaar),
+            5 => Some(CertificateKind::Bundle),
+            6 => Some(CertificateKind::WithPublicKey),
+            7 => Some(CertificateKind::GstPacked),
+            _ => None,
+        }
+    }
+
+    /// Reads `doc_kind` from ABI-encoded public values without decoding the whole struct
+    pub fn from_public_values(public_values: &[u8]) -> Option<Self> {
+        let kind = public_values_header_word(public_values, 1)?;
+        Self::from_u8(u8::try_from(kind).ok()?)
+    }
+}
+
+/// Reads `schema_version` from ABI-encoded public values without decoding the whole struct
+pub fn public_values_schema_version(public_values: &[u8]) -> Option<u16> {
+    u16::try_from(public_values_header_word(public_values, 0)?).ok()
+}
+
+/// Reads the `index`th static field of the committed struct as an integer.
+///
+/// Dynamic public values structs are encoded as the offset of the struct, always 0x20,
+/// followed by the struct, whose leading words are `schema_version` and `doc_kind`. The
+/// static `GSTValuesPackedStruct` has no offset and starts with those words directly,
+/// which is unambiguous while schema versions stay below 0x20.
+fn public_values_header_word(public_values: &[u8], index: usize) -> Option<u64> {
+    let word = |offset: usize| public_values.get(offset..offset.checked_add(32)?);
+    let small_int = |word: &[u8]| {
+        if word[..24].iter().any(|&b| b != 0) {
+            return None;
+        }
+        Some(u64::from_be_bytes(word[24..].try_into().ok()?))
+    };
+
+    let first = small_int(word(0)?)?;
+    let second = small_int(word(32)?)?;
+    if first != 0x20 && second == CertificateKind::GstPacked as u64 {
+        return small_int(word(index.checked_mul(32)?)?);
+    }
+    let offset = usize::try_from(first).ok()?;
+    small_int(word(offset.checked_add(index.checked_mul(32)?)?)?)
+}
+
+/// Public values decoded into the struct matching their `doc_kind`
+pub enum DecodedDocument {
+    Gst(GSTValuesStruct),
+    GstPrivate(GSTValuesStructPrivate),
+    Pan(PANValuesStruct),
+    DrivingLicense(DLValuesStruct),
+    Aadhaar(AadhaarValuesStruct),
+    Bundle(BundleValuesStruct),
+    /// Decode `values` with `decode_public_values` for the document's own fields
+    WithPublicKey(PublicKeyValuesStruct),
+    GstPacked(GSTValuesPackedStruct),
+}
+
+/// Decodes public values committed by the program, dispatching on their leading `doc_kind`.
+///
+/// Values committed under any schema other than `PUBLIC_VALUES_SCHEMA_VERSION` are rejected.
+pub fn decode_public_values(public_values: &[u8]) -> Result<DecodedDocument, PublicValuesError> {
+    match public_values_schema_version(public_values) {
+        Some(PUBLIC_VALUES_SCHEMA_VERSION) => {}
+        Some(version) => return Err(PublicValuesError::UnsupportedVersion(version)),
+        None => {
+            return Err(PublicValuesError::Malformed(
+                "missing schema_version".to_string(),
+            ))
+        }
+    }
+    let kind =
+        CertificateKind::from_public_values(public_values).ok_or(PublicValuesError::UnknownKind)?;
+    let malformed = |e: alloy_sol_types::Error| PublicValuesError::Malformed(e.to_string());
+
+    Ok(match kind {
+        CertificateKind::Gst => {
+            DecodedDocument::Gst(GSTValuesStruct::abi_decode(public_values).map_err(malformed)?)
+        }
+        CertificateKind::GstPrivate => DecodedDocument::GstPrivate(
+            GSTValuesStructPrivate::abi_decode(public_values).map_err(malformed)?,
+        ),
+        CertificateKind::Pan => {
+            DecodedDocument::Pan(PANValuesStruct::abi_decode(public_values).map_err(malformed)?)
+        }
+        CertificateKind::DrivingLicense => DecodedDocument::DrivingLicense(
+            DLValuesStruct::abi_decode(public_values).map_err(malformed)?,
+        ),
+        CertificateKind::Aadhaar => DecodedDocument::Aadhaar(
+            AadhaarValuesStruct::abi_decode(public_values).map_err(malformed)?,
+        ),
+        CertificateKind::Bundle => DecodedDocument::Bundle(
+            BundleValuesStruct::abi_decode(public_values).map_err(malformed)?,
+        ),
+        CertificateKind::WithPublicKey => DecodedDocument::WithPublicKey(
+            PublicKeyValuesStruct::abi_decode(public_values).map_err(malformed)?,
+        ),
+        CertificateKind::GstPacked => DecodedDocument::GstPacked(
+            GSTValuesPackedStruct::abi_decode(public_values).map_err(malformed)?,
+        ),
+    })
+}
+
+impl TryFrom<&[u8]> for DecodedDocument {
+    type Error = PublicValuesError;
+
+    fn try_from(public_values: &[u8]) -> Result<Self, Self::Error> {
+        decode_public_values(public_values)
+    }
+}
+
+/// GST public values, or `None` if `public_values` are malformed, from another schema
+/// version or of another document kind
+pub fn try_decode_gst(public_values: &[u8]) -> Option<GSTValuesStruct> {
+    match decode_public_values(public_values) {
+        Ok(DecodedDocument::Gst(values)) => Some(values),
+        _ => None,
+    }
+}
+
+/// Packed GST public values, or `None` like `try_decode_gst`
+pub fn try_decode_gst_packed(public_values: &[u8]) -> Option<GSTValuesPackedStruct> {
+    match decode_public_values(public_values) {
+        Ok(DecodedDocument::GstPacked(values)) => Some(values),
+        _ => None,
+    }
+}
+
+impl GSTValuesPackedStruct {
+    /// The GSTIN as text, empty when the disclosure mask hid it
+    pub fn gstin(&self) -> String {
+        String::from_utf8_lossy(self.gst_number.as_slice())
+            .trim_end_matches('\0')
+            .to_string()
+    }
+}
+
+/// PAN public values, or `None` like `try_decode_gst`
+pub fn try_decode_pan(public_values: &[u8]) -> Option<PANValuesStruct> {
+    match decode_public_values(public_values) {
+        Ok(DecodedDocument::Pan(values)) => Some(values),
+        _ => None,
+    }
+}
+
+/// Driving licence public values, or `None` like `try_decode_gst`
+pub fn try_decode_dl(public_values: &[u8]) -> Option<DLValuesStruct> {
+    match decode_public_values(public_values) {
+        Ok(DecodedDocument::DrivingLicense(values)) => Some(values),
+        _ => None,
+    }
+}
+
+/// Inputs shared by the public values encoders besides the certificate itself
+#[derive(Debug, Clone, Copy)]
+pub struct CommitContext {
+    pub disclosure: DisclosureMask,
+    /// Salt mixed into the field commitment
+    pub salt: [u8; 32],
+    /// `utils::full_document_commitment` of the verified pages
+    pub document_commitment: [u8; 32],
+    /// Whether `document_commitment` was checked against a caller-supplied value
+    pub committed_match: bool,
+    /// Commit `PublicKeyValuesStruct` with the signer's full public key instead of only
+    /// its hash
+    pub include_public_key: bool,
+    /// Commit GST values as the fixed-size `GSTValuesPackedStruct`
+    pub packed: bool,
+    /// Unix time of proving, supplied by the prover and committed as `proven_at`
+    pub proven_at: u64,
+}
+
+/// Wraps ABI-encoded public values in `PublicKeyValuesStruct` with the full `public_key`
+pub fn with_public_key(public_values: Vec<u8>, public_key: &[u8]) -> Vec<u8> {
+    PublicKeyValuesStruct::abi_encode(&PublicKeyValuesStruct {
+        schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+        doc_kind: CertificateKind::WithPublicKey as u8,
+        values: public_values.into(),
+        public_key: public_key.to_vec().into(),
+    })
+}
+
+/// Commits `public_values`, wrapped with `public_key` if the context asks for it
+#[cfg(feature = "zkvm")]
+fn commit_public_values(public_values: Vec<u8>, ctx: &CommitContext, public_key: &[u8]) {
+    if ctx.include_public_key {
+        sp1_zkvm::io::commit_slice(&with_public_key(public_values, public_key));
+    } else {
+        sp1_zkvm::io::commit_slice(&public_values);
     }
 }
 
+/// Fully revealed GST values from (certificate, field commitment, public key hash).
+///
+/// `document_commitment` and `proven_at` are left zero and `committed_match` false;
+/// `gst_public_values` sets them from its `CommitContext`.
+impl From<(&GSTCertificate, [u8; 32], [u8; 32])> for GSTValuesStruct {
+    fn from(
+        (cert, field_commitment, public_key_hash): (&GSTCertificate, [u8; 32], [u8; 32]),
+    ) -> Self {
+        GSTValuesStruct {
+            schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+            doc_kind: CertificateKind::Gst as u8,
+            gst_number: cert.gst_number.clone(),
+            legal_name: cert.legal_name.clone(),
+            trade_name: cert.trade_name.clone().unwrap_or_default(),
+            registration_timestamp: date_timestamp(cert.registration_date),
+            registration_type: cert
+                .registration_type
+                .as_ref()
+                .map(|registration_type| registration_type.as_str().to_string())
+                .unwrap_or_default(),
+            // OCR-read fields are not covered by the signature
+            signature_valid: cert.signature.is_valid && cert.text_source == TextSource::Signed,
+            field_commitment: field_commitment.into(),
+            document_commitment: [0u8; 32].into(),
+            committed_match: false,
+            public_key_hash: public_key_hash.into(),
+            proven_at: 0,
+        }
+    }
+}
+
+/// PAN values from (certificate, field commitment, public key hash), with the gender
+/// revealed and no age thresholds.
+///
+/// `document_commitment` and `proven_at` are left zero and `committed_match` false;
+/// `pan_public_values` sets those and the age proofs.
+impl From<(&PANCertificate, [u8; 32], [u8; 32])> for PANValuesStruct {
+    fn from(
+        (cert, field_commitment, public_key_hash): (&PANCertificate, [u8; 32], [u8; 32]),
+    ) -> Self {
+        PANValuesStruct {
+            schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+            doc_kind: CertificateKind::Pan as u8,
+            pan_number: cert.pan_number.clone(),
+            legal_name: cert.legal_name.clone(),
+            dob: cert.dob.clone(),
+            print_timestamp: date_timestamp(cert.print_date),
+            gender: gender_code(cert.gender),
+            age_thresholds: Vec::new(),
+            age_over_thresholds: Vec::new(),
+            age_as_of: String::new(),
+            signature_valid: cert.signature.is_valid,
+            field_commitment: field_commitment.into(),
+            document_commitment: [0u8; 32].into(),
+            committed_match: false,
+            public_key_hash: public_key_hash.into(),
+            proven_at: 0,
+        }
+    }
+}
+
+/// ABI-encodes the GST public values, as `GSTValuesPackedStruct` when the context asks
+/// for it, as `GSTValuesStruct` when the mask reveals every field and as
+/// `GSTValuesStructPrivate` otherwise
+pub fn gst_public_values(
+    cert: &GSTCertificate,
+    ctx: &CommitContext,
+) -> Result<Vec<u8>, CommitmentError> {
+    if ctx.packed {
+        return gst_packed_public_values(cert, ctx);
+    }
+    let public_key_hash = keccak256(&cert.signature.public_key).0;
+
+    if ctx.disclosure.reveals_all() {
+        cycle_tracker!("start", "commitment");
+        let field_commitment = gst_generate_commitment(cert, ctx.salt)?;
+        cycle_tracker!("end", "commitment");
+
+        let mut values = GSTValuesStruct::from((cert, field_commitment, public_key_hash));
+        values.document_commitment = ctx.document_commitment.into();
+        values.committed_match = ctx.committed_match;
+        values.proven_at = ctx.proven_at;
+        return Ok(GSTValuesStruct::abi_encode(&values));
+    }
+
+    cycle_tracker!("start", "commitment");
+    let field_commitment = gst_generate_commitment_masked(cert, &ctx.disclosure, ctx.salt)?;
+    cycle_tracker!("end", "commitment");
+    let reveal = |revealed: bool, value: &str| {
+        if revealed {
+            value.to_string()
+        } else {
+            String::new()
+        }
+    };
+
+    Ok(GSTValuesStructPrivate::abi_encode(
+        &GSTValuesStructPrivate {
+            schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+            doc_kind: CertificateKind::GstPrivate as u8,
+            gst_number: reveal(ctx.disclosure.gst_number, &cert.gst_number),
+            gst_number_commitment: salted_field_commitment(
+                ctx.salt,
+                b"gst_number",
+                cert.gst_number.as_bytes(),
+            )
+            .into(),
+            legal_name: reveal(ctx.disclosure.legal_name, &cert.legal_name),
+            legal_name_commitment: salted_field_commitment(
+                ctx.salt,
+                b"legal_name",
+                cert.legal_name.as_bytes(),
+            )
+            .into(),
+            signature_valid: cert.signature.is_valid,
+            field_commitment: field_commitment.into(),
+            document_commitment: ctx.document_commitment.into(),
+            committed_match: ctx.committed_match,
+            public_key_hash: public_key_hash.into(),
+            proven_at: ctx.proven_at,
+        },
+    ))
+}
+
+/// ABI-encodes the GST public values as `GSTValuesPackedStruct`
+pub fn gst_packed_public_values(
+    cert: &GSTCertificate,
+    ctx: &CommitContext,
+) -> Result<Vec<u8>, CommitmentError> {
+    cycle_tracker!("start", "commitment");
+    let field_commitment = gst_generate_commitment_masked(cert, &ctx.disclosure, ctx.salt)?;
+    cycle_tracker!("end", "commitment");
+
+    // GSTINs are 15 ASCII characters; anything longer would not have been extracted
+    let mut gst_number = [0u8; 15];
+    if ctx.disclosure.gst_number {
+        let bytes = cert.gst_number.as_bytes();
+        let len = bytes.len().min(gst_number.len());
+        gst_number[..len].copy_from_slice(&bytes[..len]);
+    }
+
+    Ok(GSTValuesPackedStruct::abi_encode(&GSTValuesPackedStruct {
+        schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+        doc_kind: CertificateKind::GstPacked as u8,
+        gst_number: gst_number.into(),
+        state_code: cert.state_code,
+        legal_name_commitment: salted_field_commitment(
+            ctx.salt,
+            b"legal_name",
+            cert.legal_name.as_bytes(),
+        )
+        .into(),
+        registration_timestamp: date_timestamp(cert.registration_date),
+        signature_valid: cert.signature.is_valid,
+        field_commitment: field_commitment.into(),
+        document_commitment: ctx.document_commitment.into(),
+        committed_match: ctx.committed_match,
+        public_key_hash: keccak256(&cert.signature.public_key).0.into(),
+        proven_at: ctx.proven_at,
+    }))
+}
+
+/// ABI-encodes the PAN public values together with the already evaluated age thresholds
+pub fn pan_public_values(
+    cert: &PANCertificate,
+    ctx: &CommitContext,
+    age_thresholds: Vec<u32>,
+    age_over_thresholds: Vec<bool>,
+    age_as_of: String,
+) -> Result<Vec<u8>, CommitmentError> {
+    cycle_tracker!("start", "commitment");
+    let field_commitment = pan_generate_commitment_masked(cert, &ctx.disclosure, ctx.salt)?;
+    cycle_tracker!("end", "commitment");
+
+    let public_key_hash = keccak256(&cert.signature.public_key).0;
+    let mut values = PANValuesStruct::from((cert, field_commitment, public_key_hash));
+    if !ctx.disclosure.gender {
+        values.gender = GENDER_WITHHELD;
+    }
+    values.age_thresholds = age_thresholds;
+    values.age_over_thresholds = age_over_thresholds;
+    values.age_as_of = age_as_of;
+    values.document_commitment = ctx.document_commitment.into();
+    values.committed_match = ctx.committed_match;
+    values.proven_at = ctx.proven_at;
+    Ok(PANValuesStruct::abi_encode(&values))
+}
+
+/// ABI-encodes the driving license public values
+pub fn dl_public_values(
+    cert: &DLCertificate,
+    ctx: &CommitContext,
+) -> Result<Vec<u8>, CommitmentError> {
+    cycle_tracker!("start", "commitment");
+    let field_commitment = dl_generate_commitment(cert, ctx.salt)?;
+    cycle_tracker!("end", "commitment");
+
+    Ok(DLValuesStruct::abi_encode(&DLValuesStruct {
+        schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+        doc_kind: CertificateKind::DrivingLicense as u8,
+        dl_number: cert.dl_number.clone(),
+        holder_name: cert.holder_name.clone(),
+        issuing_authority: cert.issuing_authority.clone(),
+        license_type: cert.license_type.clone(),
+        expiry_date: cert.expiry_date.clone(),
+        signature_valid: cert.signature.is_valid,
+        field_commitment: field_commitment.into(),
+        document_commitment: ctx.document_commitment.into(),
+        committed_match: ctx.committed_match,
+        public_key_hash: keccak256(&cert.signature.public_key).0.into(),
+        proven_at: ctx.proven_at,
+    }))
+}
+
+/// ABI-encodes the e-Aadhaar public values
+pub fn aadhaar_public_values(
+    cert: &AadhaarCertificate,
+    ctx: &CommitContext,
+) -> Result<Vec<u8>, CommitmentError> {
+    cycle_tracker!("start", "commitment");
+    let field_commitment = aadhaar_generate_commitment(cert, ctx.salt)?;
+    cycle_tracker!("end", "commitment");
+
+    Ok(AadhaarValuesStruct::abi_encode(&AadhaarValuesStruct {
+        schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+        doc_kind: CertificateKind::Aadhaar as u8,
+        masked_uid_last4: cert.masked_uid_last4.clone(),
+        name: cert.name.clone(),
+        dob: cert.dob.clone(),
+        gender: gender_code(cert.gender),
+        signature_valid: cert.signature.is_valid,
+        field_commitment: field_commitment.into(),
+        document_commitment: ctx.document_commitment.into(),
+        committed_match: ctx.committed_match,
+        public_key_hash: keccak256(&cert.signature.public_key).0.into(),
+        proven_at: ctx.proven_at,
+    }))
+}
+
+/// ABI-encodes the public values of several documents proven together.
+///
+/// Each document contributes the field commitment its single-document encoder would
+/// commit, its full-text commitment and its signer key hash; `all_signatures_valid` is
+/// false if any signature failed. `proven_at` is the prover-supplied
+/// proving time, as in `CommitContext`.
+pub fn bundle_public_values(
+    documents: &[VerifiedDocument],
+    disclosure: &DisclosureMask,
+    salt: [u8; 32],
+    proven_at: u64,
+) -> Result<Vec<u8>, CommitmentError> {
+    cycle_tracker!("start", "commitment");
+    let field_commitments = documents
+        .iter()
+        .map(|document| {
+            document
+                .certificate
+                .field_commitment(disclosure, salt)
+                .map(Into::into)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    cycle_tracker!("end", "commitment");
+
+    Ok(BundleValuesStruct::abi_encode(&BundleValuesStruct {
+        schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+        doc_kind: CertificateKind::Bundle as u8,
+        doc_kinds: documents
+            .iter()
+            .map(|document| document.certificate.kind() as u8)
+            .collect(),
+        field_commitments,
+        document_commitments: documents
+            .iter()
+            .map(|document| document.document_commitment.into())
+            .collect(),
+        public_key_hashes: documents
+            .iter()
+            .map(|document| {
+                keccak256(&document.certificate.signature().public_key)
+                    .0
+                    .into()
+            })
+            .collect(),
+        all_signatures_valid: documents
+            .iter()
+            .all(|document| document.certificate.signature().is_valid),
+        proven_at,
+    }))
+}
+
+/// Commits the GST public values from `gst_public_values` as the program's output
+#[cfg(feature = "zkvm")]
+pub fn commit_gst(cert: &GSTCertificate, ctx: &CommitContext) {
+    let public_values = gst_public_values(cert, ctx)
+        .unwrap_or_else(|err| panic!("Cannot commit to GST certificate: {}", err));
+    commit_public_values(public_values, ctx, &cert.signature.public_key);
+}
+
+/// Commits the PAN public values from `pan_public_values` as the program's output
+#[cfg(feature = "zkvm")]
+pub fn commit_pan(
+    cert: &PANCertificate,
+    ctx: &CommitContext,
+    age_thresholds: Vec<u32>,
+    age_over_thresholds: Vec<bool>,
+    age_as_of: String,
+) {
+    let public_values =
+        pan_public_values(cert, ctx, age_thresholds, age_over_thresholds, age_as_of)
+            .unwrap_or_else(|err| panic!("Cannot commit to PAN certificate: {}", err));
+    commit_public_values(public_values, ctx, &cert.signature.public_key);
+}
+
+/// Commits the driving license public values from `dl_public_values` as the program's output
+#[cfg(feature = "zkvm")]
+pub fn commit_dl(cert: &DLCertificate, ctx: &CommitContext) {
+    let public_values = dl_public_values(cert, ctx)
+        .unwrap_or_else(|err| panic!("Cannot commit to DL certificate: {}", err));
+    commit_public_values(public_values, ctx, &cert.signature.public_key);
+}
+
+/// Commits the e-Aadhaar public values from `aadhaar_public_values` as the program's output
+#[cfg(feature = "zkvm")]
+pub fn commit_aadhaar(cert: &AadhaarCertificate, ctx: &CommitContext) {
+    let public_values = aadhaar_public_values(cert, ctx)
+        .unwrap_or_else(|err| panic!("Cannot commit to Aadhaar certificate: {}", err));
+    commit_public_values(public_values, ctx, &cert.signature.public_key);
+}
+
+/// Commits the public values from `bundle_public_values` as the program's output
+#[cfg(feature = "zkvm")]
+pub fn commit_bundle(
+    documents: &[VerifiedDocument],
+    disclosure: &DisclosureMask,
+    salt: [u8; 32],
+    proven_at: u64,
+) {
+    let public_values = bundle_public_values(documents, disclosure, salt, proven_at)
+        .unwrap_or_else(|err| panic!("Cannot commit to document bundle: {}", err));
+    sp1_zkvm::io::commit_slice(&public_values);
+}
+
 /// GST Certificate verification function that extracts legal name and GST number
 pub fn verify_gst_certificate(pdf_bytes: Vec<u8>) -> Result<GSTCertificate, GSTVerificationError> {
-    let verified_content = verify_and_extract(pdf_bytes)
-        .map_err(|e| GSTVerificationError::PdfVerificationFailed(e.to_string()))?;
+    verify_gst_pdf(pdf_bytes).and_then(|(content, certificate)| {
+        gst_extraction_from_verified(content, certificate.as_deref())
+            .map(|extraction| extraction.certificate)
+    })
+}
 
-    let full_text = verified_content.pages.join(" ");
+/// Extracts the GST certificate from content the caller already got from zkpdf's
+/// `verify_and_extract`, e.g. a cached result shared across document-type detection.
+///
+/// Skips the size, signed byte range and signature checks of `verify_gst_certificate`,
+/// so `content` must be the unmodified result of verifying the PDF.
+pub fn gst_from_verified(
+    content: PdfVerifiedContent,
+) -> Result<GSTCertificate, GSTVerificationError> {
+    gst_extraction_from_verified(content, None).map(|extraction| extraction.certificate)
+}
 
-    let gst_pattern =
-        regex::Regex::new(r"([0-9]{2}[A-Z]{5}[0-9]{4}[A-Z]{1}[1-9A-Z]{1}[Z]{1}[0-9A-Z]{1})")
-            .map_err(|e| GSTVerificationError::RegexCompilationFailed(e.to_string()))?;
+fn gst_extraction_from_verified(
+    content: PdfVerifiedContent,
+    certificate: Option<&[u8]>,
+) -> Result<GSTExtraction, GSTVerificationError> {
+    extract_gst_certificate(
+        &verified_gst_text(&content)?,
+        content.signature,
+        certificate,
+        &CertificateVerifier::default(),
+    )
+    .map(|extraction| with_annexure(extraction, &content.pages))
+}
 
-    let gst_number = gst_pattern
-        .captures(&full_text)
-        .and_then(|cap| cap.get(1))
-        .map(|m| m.as_str().to_string())
+/// Adds the REG-06 annexure pages to an extracted certificate
+fn with_annexure(mut extraction: GSTExtraction, pages: &[String]) -> GSTExtraction {
+    extraction.certificate.annexure = extract_annexure(pages);
+    extraction
+}
+
+/// Verifies each GST PDF independently and returns one result per input, in order.
+///
+/// A failing PDF does not stop the rest of the batch. This is meant for off-chain
+/// callers; the zkVM program proves a single PDF and should not use it. With the
+/// `parallel` feature the PDFs are verified concurrently on the rayon thread pool.
+pub fn verify_gst_batch(pdfs: &[Vec<u8>]) -> Vec<Result<GSTCertificate, GSTVerificationError>> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        pdfs.par_iter()
+            .map(|pdf| verify_gst_certificate(pdf.clone()))
+            .collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        pdfs.iter()
+            .map(|pdf| verify_gst_certificate(pdf.clone()))
+            .collect()
+    }
+}
+
+/// Like `verify_gst_certificate`, but reads the PDF from `reader`.
+///
+/// Off-chain only; the zkVM program keeps passing bytes. Wrap the reader in
+/// `Read::take` to cap how much is read.
+pub fn verify_gst_certificate_from_reader<R: Read>(
+    mut reader: R,
+) -> Result<GSTCertificate, GSTVerificationError> {
+    let mut pdf_bytes = Vec::new();
+    reader
+        .read_to_end(&mut pdf_bytes)
+        .map_err(|e| GSTVerificationError::ReadFailed(e.to_string()))?;
+
+    verify_gst_certificate(pdf_bytes)
+}
+
+/// Like `verify_gst_certificate`, falling back to OCR when the PDF has no text layer.
+///
+/// The signature is verified either way, but it covers the PDF bytes, not what an OCR
+/// engine reads from the rendered pages: OCR-recovered fields cannot be bound to the
+/// signature and may be misread. Such certificates carry `text_source: TextSource::Ocr`
+/// and callers should treat them as reduced assurance, e.g. by asking for manual review.
+/// They cannot be committed (`CommitmentError::OcrText`), so they never reach public
+/// values as signed. Off-chain only; the circuit never runs OCR.
+#[cfg(feature = "ocr")]
+pub fn verify_gst_certificate_with_ocr(
+    pdf_bytes: Vec<u8>,
+) -> Result<GSTCertificate, GSTVerificationError> {
+    let (content, certificate) = verify_gst_pdf(pdf_bytes.clone())?;
+    if has_extractable_text(&content.pages) {
+        return gst_extraction_from_verified(content, certificate.as_deref())
+            .map(|extraction| extraction.certificate);
+    }
+
+    let pages = ocr::ocr_pages(&pdf_bytes).map_err(GSTVerificationError::OcrFailed)?;
+    if !has_extractable_text(&pages) {
+        return Err(GSTVerificationError::NoExtractableText);
+    }
+    let mut extraction = extract_gst_certificate(
+        &pages.join(" "),
+        content.signature,
+        certificate.as_deref(),
+        &CertificateVerifier::default(),
+    )?;
+    extraction.certificate.annexure = extract_annexure(&pages);
+    extraction.certificate.text_source = TextSource::Ocr;
+    Ok(extraction.certificate)
+}
+
+/// Like `verify_gst_certificate`, but also reports the byte offsets of the GSTIN and
+/// legal name within the pages joined by a single space
+pub fn verify_gst_certificate_detailed(
+    pdf_bytes: Vec<u8>,
+) -> Result<GSTExtraction, GSTVerificationError> {
+    verify_gst_pdf(pdf_bytes).and_then(|(content, certificate)| {
+        gst_extraction_from_verified(content, certificate.as_deref())
+    })
+}
+
+/// `verify_signed_pdf`, reporting failures as GST errors
+fn verify_gst_pdf(
+    pdf_bytes: Vec<u8>,
+) -> Result<(PdfVerifiedContent, Option<Vec<u8>>), GSTVerificationError> {
+    Ok(verify_signed_pdf(pdf_bytes)?)
+}
+
+/// Page text of verified content, joined by a single space, if it has any to extract
+fn verified_gst_text(content: &PdfVerifiedContent) -> Result<String, GSTVerificationError> {
+    if !has_extractable_text(&content.pages) {
+        return Err(GSTVerificationError::NoExtractableText);
+    }
+
+    Ok(content.pages.join(" "))
+}
+
+/// **Not a trust path.** Extracts GST fields from the PDF text without verifying its
+/// signature, for checking extraction patterns during development and in tests.
+///
+/// The returned certificate carries a synthesized signature with `is_valid: false` and
+/// an empty public key, so it cannot be committed to.
+#[doc(hidden)]
+pub fn extract_gst_fields_unverified(
+    pdf_bytes: Vec<u8>,
+) -> Result<GSTCertificate, GSTVerificationError> {
+    if pdf_bytes.len() > MAX_PDF_BYTES {
+        return Err(GSTVerificationError::PdfTooLarge {
+            size: pdf_bytes.len(),
+            limit: MAX_PDF_BYTES,
+        });
+    }
+
+    let pages = extract_text(pdf_bytes)
+        .map_err(|e| GSTVerificationError::PdfParseFailed(format!("{:?}", e)))?;
+
+    if !has_extractable_text(&pages) {
+        return Err(GSTVerificationError::NoExtractableText);
+    }
+
+    let unverified_signature = PdfSignatureResult {
+        is_valid: false,
+        message_digest: Vec::new(),
+        public_key: Vec::new(),
+    };
+
+    extract_gst_certificate(
+        &pages.join(" "),
+        unverified_signature,
+        None,
+        &CertificateVerifier::default(),
+    )
+    .map(|extraction| with_annexure(extraction, &pages).certificate)
+}
+
+/// Like `verify_gst_certificate`, but a missing legal name is reported as a warning
+/// instead of an error, alongside anything else the caller may want to be stricter about.
+///
+/// The certificate is `None` only when no usable GSTIN is found or the PDF fails
+/// verification; the cause is then the last warning, `ExtractionWarning::ExtractionFailed`.
+/// A certificate without a legal name has it left empty.
+pub fn verify_gst_certificate_lenient(
+    pdf_bytes: Vec<u8>,
+) -> (Option<GSTCertificate>, Vec<ExtractionWarning>) {
+    let mut warnings = Vec::new();
+    let extraction = verify_gst_pdf(pdf_bytes).and_then(|(verified_content, certificate)| {
+        extract_gst_certificate_with_warnings(
+            &verified_gst_text(&verified_content)?,
+            verified_content.signature,
+            certificate.as_deref(),
+            &CertificateVerifier::default(),
+            true,
+            &mut warnings,
+        )
+        .map(|extraction| with_annexure(extraction, &verified_content.pages))
+    });
+
+    match extraction {
+        Ok(extraction) => (Some(extraction.certificate), warnings),
+        Err(err) => {
+            warnings.push(ExtractionWarning::ExtractionFailed(err.to_string()));
+            (None, warnings)
+        }
+    }
+}
+
+/// What a PDF signature reveals about its signer, read once per certificate
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerInfo {
+    pub key_info: Option<KeyInfo>,
+    pub issuer: Option<String>,
+    /// Signer certificate (notBefore, notAfter), when the PDF carries the certificate
+    pub validity: Option<(NaiveDate, NaiveDate)>,
+    /// Hash algorithm of the signed digest, for enforcing a minimum strength
+    #[serde(default)]
+    pub digest_algorithm: DigestAlgo,
+    /// Whether the signer certificate chains to a CA pinned in `issuers`; always false
+    /// without the `embedded-issuers` feature
+    #[serde(default)]
+    pub chain_valid: bool,
+    /// Whether the signer certificate issued itself, so the signature vouches for nothing
+    #[serde(default)]
+    pub self_signed: bool,
+}
+
+impl SignerInfo {
+    /// Reads the signer from its signature and, when the PDF carries it, its certificate
+    /// from `utils::signer_certificate`; without one the certificate fields stay empty
+    pub fn new(signature: &PdfSignatureResult, certificate: Option<&[u8]>) -> Self {
+        SignerInfo {
+            key_info: signature_key_info(signature).ok(),
+            issuer: certificate.and_then(signer_common_name),
+            validity: certificate.and_then(signer_cert_validity),
+            digest_algorithm: digest_algorithm(signature),
+            chain_valid: signer_chain_valid(certificate),
+            self_signed: certificate.is_some_and(signer_is_self_signed),
+        }
+    }
+}
+
+/// The checks every verifier runs before extracting fields: the size limit, encryption,
+/// the signed byte range and zkpdf's signature verification. Returns the verified content
+/// with the signer certificate.
+fn verify_signed_pdf(
+    pdf_bytes: Vec<u8>,
+) -> Result<(PdfVerifiedContent, Option<Vec<u8>>), SignedPdfError> {
+    if pdf_bytes.len() > MAX_PDF_BYTES {
+        return Err(SignedPdfError::PdfTooLarge {
+            size: pdf_bytes.len(),
+            limit: MAX_PDF_BYTES,
+        });
+    }
+    if is_encrypted_pdf(&pdf_bytes) {
+        return Err(SignedPdfError::Encrypted);
+    }
+    assert_text_within_signed_range(&pdf_bytes).map_err(SignedPdfError::Tampered)?;
+
+    verify_and_extract_signer(pdf_bytes).map_err(SignedPdfError::VerificationFailed)
+}
+
+/// zkpdf's `verify_and_extract`, also returning the signer certificate, which zkpdf
+/// verifies the signature with but does not return
+fn verify_and_extract_signer(
+    pdf_bytes: Vec<u8>,
+) -> Result<(PdfVerifiedContent, Option<Vec<u8>>), String> {
+    let certificates = embedded_certificates(&pdf_bytes);
+    let content = verify_and_extract(pdf_bytes).map_err(|e| e.to_string())?;
+    let certificate = signer_certificate(&certificates, &content.signature).map(<[u8]>::to_vec);
+    Ok((content, certificate))
+}
+
+/// Whether the signer certificate chains to a pinned issuer CA
+#[cfg(feature = "embedded-issuers")]
+fn signer_chain_valid(certificate: Option<&[u8]>) -> bool {
+    certificate.is_some_and(|certificate| issuers::verify_signer_chain(certificate).is_ok())
+}
+
+/// Without pinned issuers no chain can be checked
+#[cfg(not(feature = "embedded-issuers"))]
+fn signer_chain_valid(_certificate: Option<&[u8]>) -> bool {
+    false
+}
+
+/// Extracts the GST certificate fields and their spans from already verified PDF text
+fn extract_gst_certificate(
+    full_text: &str,
+    signature: PdfSignatureResult,
+    certificate: Option<&[u8]>,
+    verifier: &CertificateVerifier,
+) -> Result<GSTExtraction, GSTVerificationError> {
+    extract_gst_certificate_with_warnings(
+        full_text,
+        signature,
+        certificate,
+        verifier,
+        false,
+        &mut Vec::new(),
+    )
+}
+
+/// Shared GST extraction; when `lenient`, a missing legal name becomes a warning
+fn extract_gst_certificate_with_warnings(
+    full_text: &str,
+    signature: PdfSignatureResult,
+    certificate: Option<&[u8]>,
+    verifier: &CertificateVerifier,
+    lenient: bool,
+    warnings: &mut Vec<ExtractionWarning>,
+) -> Result<GSTExtraction, GSTVerificationError> {
+    let (full_text, truncated) = truncate_scan_text(full_text, verifier.max_scan_chars);
+    if truncated {
+        log::debug!(
+            "GST: text truncated to {} characters",
+            verifier.max_scan_chars
+        );
+        warnings.push(ExtractionWarning::TextTruncated {
+            max_chars: verifier.max_scan_chars,
+        });
+    }
+
+    if let Some(allowlist) = &verifier.trusted_issuers {
+        let public_key_hash: [u8; 32] = keccak256(&signature.public_key).into();
+        if !is_trusted_issuer(&public_key_hash, allowlist) {
+            log::debug!("GST: signer is not a trusted issuer");
+            return Err(GSTVerificationError::UntrustedIssuer);
+        }
+    }
+
+    let mut all_gst_numbers = extract_all_gstins(full_text);
+    // Special taxpayer types are always reported, but only used when opted in
+    for (gstin, category) in extract_special_gstins(full_text) {
+        if verifier.accept_special_gstins {
+            all_gst_numbers.push(gstin.clone());
+        }
+        warnings.push(ExtractionWarning::SpecialGstin {
+            gstin,
+            category,
+            accepted: verifier.accept_special_gstins,
+        });
+    }
+    // Misread digits are only corrected when opted in, and only to checksum-valid GSTINs
+    let corrections = if verifier.fuzzy {
+        extract_confusable_gstins(full_text)
+    } else {
+        Vec::new()
+    };
+    for (printed, corrected) in &corrections {
+        if !all_gst_numbers.contains(corrected) {
+            all_gst_numbers.push(corrected.clone());
+        }
+        warnings.push(ExtractionWarning::ConfusablesCorrected {
+            printed: printed.clone(),
+            corrected: corrected.clone(),
+        });
+    }
+    if all_gst_numbers.is_empty() {
+        return Err(GSTVerificationError::GSTNumberNotFound);
+    }
+
+    // The first GSTIN with a valid checksum is the primary registration
+    if !verifier.validate_checksum {
+        warnings.push(ExtractionWarning::ChecksumSkipped);
+    }
+    let (valid_gst_numbers, invalid_gst_numbers): (Vec<_>, Vec<_>) = all_gst_numbers
+        .into_iter()
+        .partition(|gstin| !verifier.validate_checksum || validate_gstin_checksum(gstin));
+    log::debug!(
+        "GST: {} GSTIN(s) with a valid checksum, {} invalid",
+        valid_gst_numbers.len(),
+        invalid_gst_numbers.len()
+    );
+    warnings.extend(
+        invalid_gst_numbers
+            .into_iter()
+            .map(ExtractionWarning::InvalidChecksum),
+    );
+    let mut valid_gst_numbers = valid_gst_numbers.into_iter();
+    let gst_number = valid_gst_numbers
+        .next()
+        .ok_or(GSTVerificationError::InvalidChecksum)?;
+    log_field("GST", "GSTIN", Some(&gst_number));
+    let additional_gst_numbers: Vec<String> = valid_gst_numbers.collect();
+    if !additional_gst_numbers.is_empty() {
+        warnings.push(ExtractionWarning::MultipleGstinsFound(
+            additional_gst_numbers.len() + 1,
+        ));
+    }
+
+    // Primary GSTIN is reported at its first occurrence, as printed if it was corrected
+    let gst_number_start = full_text
+        .find(gst_number.as_str())
+        .or_else(|| {
+            corrections
+                .iter()
+                .find(|(_, corrected)| *corrected == gst_number)
+                .and_then(|(printed, _)| full_text.find(printed.as_str()))
+        })
         .ok_or(GSTVerificationError::GSTNumberNotFound)?;
+    let gst_number_span = FieldSpan {
+        start: gst_number_start,
+        end: gst_number_start + gst_number.len(),
+        value: gst_number.clone(),
+    };
 
-    let legal_name_pattern =
-        regex::Regex::new(r"Legal Name\s*([A-Za-z\s&.,]+?)(?:\n|Trade Name|Additional|$)")
-            .map_err(|e| GSTVerificationError::RegexCompilationFailed(e.to_string()))?;
+    // The first two digits of a GSTIN identify the registering state
+    let state_code = gst_number[..2]
+        .parse::<u8>()
+        .map_err(|_| GSTVerificationError::GSTNumberNotFound)?;
+    let state_name = gstin_state(state_code).map(str::to_string);
 
-    let legal_name = legal_name_pattern
-        .captures(&full_text)
+    let legal_name_span = verifier
+        .legal_name_regex
+        .as_ref()
+        .unwrap_or(&GST_LEGAL_NAME_REGEX)
+        .captures(full_text)
         .and_then(|cap| cap.get(1))
-        .map(|m| m.as_str().trim().to_string())
-        .ok_or(GSTVerificationError::LegalNameNotFound)?;
+        .map(|m| {
+            // Stop at the trade name header when the portal prints both on one line
+            let end = verifier
+                .trade_name_regex
+                .as_ref()
+                .and_then(|trade_name| trade_name.find(m.as_str()))
+                .map_or(m.end(), |header| m.start() + header.start());
+            FieldSpan {
+                start: m.start(),
+                end,
+                value: full_text[m.start()..end].to_string(),
+            }
+        });
+    let legal_name_span = match legal_name_span {
+        Some(span) => span,
+        None if lenient => {
+            warnings.push(ExtractionWarning::MissingLegalName);
+            FieldSpan {
+                start: 0,
+                end: 0,
+                value: String::new(),
+            }
+        }
+        None => return Err(GSTVerificationError::LegalNameNotFound),
+    };
+    let legal_name = normalize_bilingual_name(&legal_name_span.value);
+    log_field(
+        "GST",
+        "legal name",
+        Some(legal_name.as_str()).filter(|name| !name.is_empty()),
+    );
 
-    Ok(GSTCertificate {
-        gst_number,
-        legal_name,
-        signature: verified_content.signature,
+    // Trade name is optional on the certificate
+    let trade_name = verifier
+        .trade_name_regex
+        .as_ref()
+        .unwrap_or(&GST_TRADE_NAME_REGEX)
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| normalize_name(m.as_str()))
+        .filter(|name| !name.is_empty());
+    log_field("GST", "trade name", trade_name.as_deref());
+
+    let registration_date = GST_REGISTRATION_DATE_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| parse_indian_dob(m.as_str()).ok());
+    log_field("GST", "registration date", registration_date);
+    if registration_date.is_none() {
+        warnings.push(ExtractionWarning::MissingRegistrationDate);
+    }
+
+    let constitution = GST_CONSTITUTION_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().trim())
+        .filter(|raw| !raw.is_empty())
+        .map(parse_business_constitution);
+    log_field("GST", "constitution", constitution.as_ref());
+
+    let registration_type = GST_REGISTRATION_TYPE_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().trim())
+        .filter(|raw| !raw.is_empty())
+        .map(parse_registration_type);
+    log_field("GST", "registration type", registration_type.as_ref());
+
+    // Principal place of business, printed across several lines
+    let address = GST_ADDRESS_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| normalize_address(m.as_str()))
+        .filter(|address| !address.is_empty());
+    log_field("GST", "address", address.as_deref());
+
+    let contact_field = |regex: &Regex| {
+        verifier
+            .extract_contact
+            .then(|| regex.captures(full_text))
+            .flatten()
+            .and_then(|cap| cap.get(1))
+            .map(|m| m.as_str().to_string())
+    };
+    let email = contact_field(&GST_EMAIL_REGEX);
+    let mobile_last4 = contact_field(&GST_MOBILE_LAST4_REGEX);
+    if verifier.extract_contact {
+        log_field("GST", "email", email.as_deref());
+        log_field("GST", "mobile", mobile_last4.as_deref());
+    }
+
+    Ok(GSTExtraction {
+        certificate: GSTCertificate {
+            gst_number,
+            additional_gst_numbers,
+            legal_name_variants: name_variants(&legal_name),
+            // Filled in by `with_annexure` where the separate pages are available
+            annexure: None,
+            text_source: TextSource::Signed,
+            legal_name,
+            trade_name,
+            registration_date,
+            address,
+            constitution,
+            registration_type,
+            email,
+            mobile_last4,
+            state_code,
+            state_name,
+            signer: SignerInfo::new(&signature, certificate),
+            signature,
+        },
+        gst_number_span,
+        legal_name_span,
     })
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct PANCertificate {
     pub pan_number: String,
     pub legal_name: String,
+    pub fathers_name: Option<String>,
+    pub gender: Option<Gender>,
+    #[serde(with = "crate::utils::signature_serde")]
     pub signature: PdfSignatureResult,
+    #[serde(default)]
+    pub signer: SignerInfo,
     pub dob: String,
+    /// Issue or print date, printed only on newer e-PANs
+    pub print_date: Option<NaiveDate>,
+    pub category: PanCategory,
+}
+
+impl PANCertificate {
+    /// One-line summary with the PAN masked to its last four characters
+    pub fn redacted(&self) -> String {
+        self.summary(&mask_identifier(&self.pan_number))
+    }
+
+    /// One-line summary with the full PAN, for when the raw value is explicitly wanted
+    pub fn full_display(&self) -> String {
+        self.summary(&self.pan_number)
+    }
+
+    fn summary(&self, pan_number: &str) -> String {
+        format!(
+            "PAN {} | {} | {:?} | signature {}",
+            pan_number,
+            self.legal_name,
+            self.category,
+            if self.signature.is_valid {
+                "valid"
+            } else {
+                "invalid"
+            }
+        )
+    }
+}
+
+/// Redacted by default so PANs don't leak into logs
+impl fmt::Display for PANCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.redacted())
+    }
+}
+
+impl fmt::Debug for PANCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PANCertificate")
+            .field("pan_number", &mask_identifier(&self.pan_number))
+            .field("legal_name", &self.legal_name)
+            .field("category", &self.category)
+            .field("gender", &self.gender)
+            .field("signature_valid", &self.signature.is_valid)
+            .field("issuer", &self.signer.issuer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Document identity, like `GSTCertificate`'s: equal when the commitments are equal, or
+/// for certificates that cannot be committed, when the PAN and signed digest are
+impl PartialEq for PANCertificate {
+    fn eq(&self, other: &Self) -> bool {
+        self.document_key() == other.document_key()
+    }
+}
+
+impl Eq for PANCertificate {}
+
+/// Hashes the document identity, consistent with `PartialEq`
+impl Hash for PANCertificate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.document_key().hash(state);
+    }
+}
+
+impl PANCertificate {
+    fn document_key(&self) -> DocumentKey<'_> {
+        document_key(self, &self.pan_number, &self.signature.message_digest)
+    }
+}
+
+/// Non-identifying facts about a verified certificate, safe to send to logs and metrics.
+///
+/// Holds no GSTIN, PAN, name, date of birth or address. The certificate does not record how
+/// many pages it was read from or when, so `page_count` and `extracted_at` are only set
+/// by callers that know them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateMetadata {
+    pub kind: CertificateKind,
+    pub signature_valid: bool,
+    pub signer: SignerInfo,
+    pub page_count: Option<usize>,
+    pub extracted_at: Option<NaiveDateTime>,
+}
+
+impl CertificateMetadata {
+    /// Records how many pages the certificate was extracted from
+    pub fn with_page_count(mut self, page_count: usize) -> Self {
+        self.page_count = Some(page_count);
+        self
+    }
+
+    /// Records when the certificate was extracted
+    pub fn with_extracted_at(mut self, extracted_at: NaiveDateTime) -> Self {
+        self.extracted_at = Some(extracted_at);
+        self
+    }
+}
+
+/// Metadata of a GST certificate, without its GSTIN, names or address
+pub fn certificate_metadata(cert: &GSTCertificate) -> CertificateMetadata {
+    CertificateMetadata {
+        kind: CertificateKind::Gst,
+        signature_valid: cert.signature.is_valid,
+        signer: cert.signer.clone(),
+        page_count: None,
+        extracted_at: None,
+    }
+}
+
+/// Metadata of a PAN certificate, without its PAN, names or date of birth
+pub fn pan_certificate_metadata(cert: &PANCertificate) -> CertificateMetadata {
+    CertificateMetadata {
+        kind: CertificateKind::Pan,
+        signature_valid: cert.signature.is_valid,
+        signer: cert.signer.clone(),
+        page_count: None,
+        extracted_at: None,
+    }
 }
 
 /// PAN Certificate verification function that extracts legal name, PAN number, and DOB
 pub fn verify_pan_certificate(pdf_bytes: Vec<u8>) -> Result<PANCertificate, PANVerificationError> {
-    let verified_content = verify_and_extract(pdf_bytes)
-        .map_err(|e| PANVerificationError::PdfVerificationFailed(e.to_string()))?;
+    verify_signed_pdf(pdf_bytes)
+        .map_err(PANVerificationError::from)
+        .and_then(|(content, certificate)| pan_from_signed(content, certificate.as_deref()))
+}
+
+/// Extracts the PAN certificate from already verified content, like `gst_from_verified`
+pub fn pan_from_verified(
+    content: PdfVerifiedContent,
+) -> Result<PANCertificate, PANVerificationError> {
+    pan_from_signed(content, None)
+}
+
+fn pan_from_signed(
+    content: PdfVerifiedContent,
+    certificate: Option<&[u8]>,
+) -> Result<PANCertificate, PANVerificationError> {
+    if !has_extractable_text(&content.pages) {
+        return Err(PANVerificationError::NoExtractableText);
+    }
+
+    extract_pan_certificate(
+        &content.pages.join(" "),
+        content.signature,
+        certificate,
+        &CertificateVerifier::default(),
+    )
+}
+
+/// Extracts the PAN certificate fields from already verified PDF text
+fn extract_pan_certificate(
+    full_text: &str,
+    signature: PdfSignatureResult,
+    certificate: Option<&[u8]>,
+    verifier: &CertificateVerifier,
+) -> Result<PANCertificate, PANVerificationError> {
+    // A PAN-shaped match with an unknown holder category is reported only if no valid
+    // PAN follows it
+    let pan_number = extract_pan(full_text);
+    log_field("PAN", "PAN", pan_number.as_deref());
+    let pan_number = pan_number.ok_or_else(|| match pan_candidates(full_text).next() {
+        Some(pan) => PANVerificationError::UnknownCategory(pan.chars().nth(3).unwrap_or_default()),
+        None => PANVerificationError::PANNumberNotFound,
+    })?;
+
+    let category = pan_holder_category(&pan_number).ok_or_else(|| {
+        PANVerificationError::UnknownCategory(pan_number.chars().nth(3).unwrap_or_default())
+    })?;
+
+    let (legal_name, fathers_name) = match &verifier.pan_name_regex {
+        Some(value_regex) => pan_names_with(full_text, value_regex),
+        None => pan_names(full_text),
+    };
+    log_field("PAN", "legal name", legal_name.as_deref());
+    log_field("PAN", "father's name", fathers_name.as_deref());
+    let legal_name = legal_name.ok_or(PANVerificationError::LegalNameNotFound)?;
+
+    let print_date_match = PAN_PRINT_DATE_REGEX.captures(full_text);
+    let print_date = print_date_match
+        .as_ref()
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| parse_indian_dob(m.as_str()).ok());
+    log_field("PAN", "print date", print_date);
+    let print_date_range = print_date_match
+        .and_then(|cap| cap.get(0))
+        .map(|m| m.range())
+        .unwrap_or_default();
+
+    // The print date can precede the DOB, so it is never taken as one
+    let dob = PAN_DOB_REGEX
+        .captures_iter(full_text)
+        .filter_map(|cap| cap.get(1))
+        .find(|m| !print_date_range.contains(&m.start()))
+        .map(|m| m.as_str().trim().to_string());
+    log_field("PAN", "DOB", dob.as_deref());
+    let dob = dob.ok_or(PANVerificationError::DOBNotFound)?;
+
+    // Only some PAN layouts print the holder's gender
+    let gender = PAN_GENDER_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| parse_gender(m.as_str()));
+    log_field("PAN", "gender", gender);
+
+    Ok(PANCertificate {
+        pan_number,
+        legal_name,
+        fathers_name,
+        gender,
+        signer: SignerInfo::new(&signature, certificate),
+        signature,
+        dob,
+        print_date,
+        category,
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DLCertificate {
+    pub dl_number: String,
+    pub holder_name: String,
+    pub issuing_authority: String,
+    pub license_type: String,
+    pub expiry_date: String,
+    #[serde(with = "crate::utils::signature_serde")]
+    pub signature: PdfSignatureResult,
+    #[serde(default)]
+    pub signer: SignerInfo,
+}
+
+/// Driving License verification function that extracts the DL number, holder name,
+/// issuing authority, class of vehicle and validity date
+pub fn verify_driving_license(pdf_bytes: Vec<u8>) -> Result<DLCertificate, DLVerificationError> {
+    let (verified_content, certificate) = verify_signed_pdf(pdf_bytes)?;
+
+    if !has_extractable_text(&verified_content.pages) {
+        return Err(DLVerificationError::NoExtractableText);
+    }
 
     let full_text = verified_content.pages.join(" ");
 
-    // Regex pattern for PAN: 5 letters + 4 digits + 1 letter
-    let pan_pattern =
-        regex::Regex::new(r"([A-Z]{5}[0-9]{4}[A-Z]{1})")
-            .map_err(|e| PANVerificationError::RegexCompilationFailed(e.to_string()))?;
+    extract_driving_license(
+        &full_text,
+        verified_content.signature,
+        certificate.as_deref(),
+    )
+}
 
-    let pan_number = pan_pattern
-        .captures(&full_text)
+/// Extracts the Driving License fields from already verified PDF text
+fn extract_driving_license(
+    full_text: &str,
+    signature: PdfSignatureResult,
+    certificate: Option<&[u8]>,
+) -> Result<DLCertificate, DLVerificationError> {
+    let dl_number = DL_NUMBER_REGEX
+        .captures(full_text)
         .and_then(|cap| cap.get(1))
         .map(|m| m.as_str().to_string())
-        .ok_or(PANVerificationError::PANNumberNotFound)?;
+        .ok_or(DLVerificationError::DLNumberNotFound)?;
 
-    // Legal name pattern (adjust keywords based on actual PDF text)
-    let legal_name_pattern =
-        regex::Regex::new(r"Name\s*([A-Za-z\s&.,]+?)(?:\n|Father|DOB|$)")
-            .map_err(|e| PANVerificationError::RegexCompilationFailed(e.to_string()))?;
+    let holder_name = DL_HOLDER_NAME_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| normalize_name(m.as_str()))
+        .ok_or(DLVerificationError::HolderNameNotFound)?;
 
-    let legal_name = legal_name_pattern
-        .captures(&full_text)
+    let issuing_authority = DL_ISSUING_AUTHORITY_REGEX
+        .captures(full_text)
         .and_then(|cap| cap.get(1))
         .map(|m| m.as_str().trim().to_string())
-        .ok_or(PANVerificationError::LegalNameNotFound)?;
+        .ok_or(DLVerificationError::IssuingAuthorityNotFound)?;
 
-    // DOB pattern (usually in DD/MM/YYYY format on PAN card)
-    let dob_pattern =
-        regex::Regex::new(r"(\d{2}/\d{2}/\d{4})")
-            .map_err(|e| PANVerificationError::RegexCompilationFailed(e.to_string()))?;
-
-    let dob = dob_pattern
-        .captures(&full_text)
+    let license_type = DL_LICENSE_TYPE_REGEX
+        .captures(full_text)
         .and_then(|cap| cap.get(1))
         .map(|m| m.as_str().trim().to_string())
-        .ok_or(PANVerificationError::DOBNotFound)?;
+        .ok_or(DLVerificationError::LicenseTypeNotFound)?;
 
-    Ok(PANCertificate {
-        pan_number,
-        legal_name,
-        signature: verified_content.signature,
+    let expiry_date = DL_EXPIRY_DATE_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().to_string())
+        .ok_or(DLVerificationError::ExpiryDateNotFound)?;
+
+    Ok(DLCertificate {
+        dl_number,
+        holder_name,
+        issuing_authority,
+        license_type,
+        expiry_date,
+        signer: SignerInfo::new(&signature, certificate),
+        signature,
+    })
+}
+
+/// e-Aadhaar verification function that extracts the masked number, name, DOB and gender.
+///
+/// The PDF must be signed by UIDAI, see `utils::is_uidai_signer`. No UIDAI key is pinned
+/// yet, so every e-Aadhaar is currently rejected as untrusted.
+#[cfg(feature = "aadhaar")]
+pub fn verify_aadhaar_certificate(
+    pdf_bytes: Vec<u8>,
+) -> Result<AadhaarCertificate, AadhaarVerificationError> {
+    let (verified_content, certificate) = verify_signed_pdf(pdf_bytes)?;
+
+    if !has_extractable_text(&verified_content.pages) {
+        return Err(AadhaarVerificationError::NoExtractableText);
+    }
+
+    let full_text = verified_content.pages.join(" ");
+
+    extract_aadhaar_certificate(
+        &full_text,
+        verified_content.signature,
+        certificate.as_deref(),
+    )
+}
+
+/// Extracts the Aadhaar fields from already verified PDF text, or fails with
+/// `AadhaarVerificationError::Disabled` without the `aadhaar` feature
+fn extract_aadhaar_certificate(
+    full_text: &str,
+    signature: PdfSignatureResult,
+    certificate: Option<&[u8]>,
+) -> Result<AadhaarCertificate, AadhaarVerificationError> {
+    if !cfg!(feature = "aadhaar") {
+        return Err(AadhaarVerificationError::Disabled);
+    }
+    if !is_uidai_signer(&signature) {
+        return Err(AadhaarVerificationError::UntrustedIssuer);
+    }
+
+    let masked_uid_last4 = AADHAAR_MASKED_UID_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().to_string())
+        .ok_or(AadhaarVerificationError::MaskedNumberNotFound)?;
+
+    let name = AADHAAR_NAME_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| normalize_name(m.as_str()))
+        .ok_or(AadhaarVerificationError::NameNotFound)?;
+
+    let dob = AADHAAR_DOB_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().to_string())
+        .ok_or(AadhaarVerificationError::DobNotFound)?;
+
+    let gender = GENDER_REGEX
+        .captures(full_text)
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| parse_gender(m.as_str()));
+
+    Ok(AadhaarCertificate {
+        masked_uid_last4,
+        name,
         dob,
+        gender,
+        signer: SignerInfo::new(&signature, certificate),
+        signature,
     })
-}
\ No newline at end of file
+}
+
+/// Identifiers and names found on a single page of a verified PDF
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageFields {
+    pub page_index: usize,
+    pub gst_numbers: Vec<String>,
+    pub pan_number: Option<String>,
+    pub legal_name: Option<String>,
+}
+
+/// Extracts the GSTINs, PAN and legal name found on each page separately.
+///
+/// Unlike the `verify_*` functions, pages are not joined, so fields from documents
+/// bundled into one PDF can be told apart by the page they appear on.
+pub fn extract_fields_per_page(verified: &PdfVerifiedContent) -> Vec<PageFields> {
+    verified
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(page_index, text)| {
+            let pan_number = extract_pan(text);
+
+            let legal_name = GST_LEGAL_NAME_REGEX
+                .captures(text)
+                .and_then(|cap| cap.get(1))
+                .map(|m| normalize_bilingual_name(m.as_str()))
+                .or_else(|| pan_names(text).0);
+
+            PageFields {
+                page_index,
+                gst_numbers: extract_all_gstins(text),
+                pan_number,
+                legal_name,
+            }
+        })
+        .collect()
+}
+
+/// Kind of document identified from the verified PDF text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentType {
+    Gst,
+    Pan,
+    DrivingLicense,
+    Aadhaar,
+    Unknown,
+}
+
+/// Detects the document type from the key markers and identifier patterns in the text.
+///
+/// The first of `detect_all_document_types`, so GST wins when several types match.
+pub fn detect_document_type(text: &str) -> DocumentType {
+    let document_type = detect_all_document_types(text)
+        .first()
+        .copied()
+        .unwrap_or(DocumentType::Unknown);
+    log::debug!("Detected document type {:?}", document_type);
+    document_type
+}
+
+/// Every document type whose markers or identifiers appear in the text, GST first, then
+/// driving license, Aadhaar and PAN; empty when none do.
+///
+/// A PAN only counts when it appears outside a GSTIN, since every GSTIN embeds the
+/// holder's PAN.
+pub fn detect_all_document_types(text: &str) -> Vec<DocumentType> {
+    let gstin_ranges: Vec<_> = GST_NUMBER_REGEX
+        .find_iter(text)
+        .map(|m| m.range())
+        .collect();
+    let has_standalone_pan = PAN_NUMBER_REGEX.find_iter(text).any(|pan| {
+        !gstin_ranges
+            .iter()
+            .any(|gstin| gstin.start <= pan.start() && pan.end() <= gstin.end)
+    });
+
+    [
+        (
+            DocumentType::Gst,
+            text.contains("Goods and Services Tax") || !gstin_ranges.is_empty(),
+        ),
+        (
+            DocumentType::DrivingLicense,
+            text.contains("Driving Licen") || DL_NUMBER_REGEX.is_match(text),
+        ),
+        (
+            DocumentType::Aadhaar,
+            text.contains("Unique Identification Authority")
+                || AADHAAR_MASKED_UID_REGEX.is_match(text),
+        ),
+        (
+            DocumentType::Pan,
+            text.contains("Permanent Account Number") || has_standalone_pan,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(document_type, matched)| matched.then_some(document_type))
+    .collect()
+}
+
+/// A recognised certificate together with the commitment to the PDF's full text
+pub struct VerifiedDocument {
+    pub certificate: CertificateOutcome,
+    /// `utils::full_document_commitment` of the verified pages
+    pub document_commitment: [u8; 32],
+    /// Soft failures noticed while reading the document, such as
+    /// `ExtractionWarning::TextTruncated`
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// A certificate recognised by `verify_certificate`
+pub enum CertificateOutcome {
+    Gst(GSTCertificate),
+    Pan(PANCertificate),
+    Dl(DLCertificate),
+    Aadhaar(AadhaarCertificate),
+}
+
+impl CertificateOutcome {
+    /// The `doc_kind` of the single-document public values; GST is always `Gst` here
+    pub fn kind(&self) -> CertificateKind {
+        match self {
+            CertificateOutcome::Gst(_) => CertificateKind::Gst,
+            CertificateOutcome::Pan(_) => CertificateKind::Pan,
+            CertificateOutcome::Dl(_) => CertificateKind::DrivingLicense,
+            CertificateOutcome::Aadhaar(_) => CertificateKind::Aadhaar,
+        }
+    }
+
+    pub fn signature(&self) -> &PdfSignatureResult {
+        match self {
+            CertificateOutcome::Gst(cert) => &cert.signature,
+            CertificateOutcome::Pan(cert) => &cert.signature,
+            CertificateOutcome::Dl(cert) => &cert.signature,
+            CertificateOutcome::Aadhaar(cert) => &cert.signature,
+        }
+    }
+
+    /// The field commitment the matching `*_public_values` encoder commits
+    pub fn field_commitment(
+        &self,
+        disclosure: &DisclosureMask,
+        salt: [u8; 32],
+    ) -> Result<[u8; 32], CommitmentError> {
+        match self {
+            CertificateOutcome::Gst(cert) if disclosure.reveals_all() => {
+                gst_generate_commitment(cert, salt)
+            }
+            CertificateOutcome::Gst(cert) => gst_generate_commitment_masked(cert, disclosure, salt),
+            CertificateOutcome::Pan(cert) => pan_generate_commitment_masked(cert, disclosure, salt),
+            CertificateOutcome::Dl(cert) => dl_generate_commitment(cert, salt),
+            CertificateOutcome::Aadhaar(cert) => aadhaar_generate_commitment(cert, salt),
+        }
+    }
+}
+
+/// Verifies a PDF of any supported type and returns its typed certificate.
+///
+/// This is the entry point most callers want: like the zkVM program, it parses and
+/// verifies the PDF once, detects the document type from the text and extracts that
+/// type's fields. Signature failures are reported as `SignatureInvalid` (or `Tampered`,
+/// `PdfParseFailed` and, when unrecognised, `PdfVerificationFailed`), extraction
+/// failures are wrapped in the error of the detected type, and a PDF that matches no
+/// supported layout yields `DocumentVerificationError::NoDocumentMatched`.
+pub fn verify_certificate(
+    pdf_bytes: Vec<u8>,
+) -> Result<CertificateOutcome, DocumentVerificationError> {
+    CertificateVerifier::default().verify(pdf_bytes)
+}
+
+/// Like `verify_certificate`, for content already verified by zkpdf's `verify_and_extract`
+pub fn certificate_from_verified(
+    content: PdfVerifiedContent,
+) -> Result<CertificateOutcome, DocumentVerificationError> {
+    CertificateVerifier::default()
+        .document_from_verified(content)
+        .map(|document| document.certificate)
+}
+
+/// Like `verify_certificate`, also committing to the full text of the verified pages
+pub fn verify_any_document(
+    pdf_bytes: Vec<u8>,
+) -> Result<VerifiedDocument, DocumentVerificationError> {
+    CertificateVerifier::default().verify_document(pdf_bytes)
+}
+
+/// Verifies a PDF bundling a GST certificate with the holder's PAN card and checks that
+/// the PAN embedded in the GSTIN is the PAN on the card.
+///
+/// Pages are classified individually, so the GSTIN's embedded PAN is never mistaken
+/// for the PAN card's number.
+pub fn verify_gst_with_pan(
+    pdf_bytes: Vec<u8>,
+) -> Result<(GSTCertificate, PANCertificate), DocumentVerificationError> {
+    let (verified_content, certificate) = verify_signed_pdf(pdf_bytes)?;
+
+    if !has_extractable_text(&verified_content.pages) {
+        return Err(DocumentVerificationError::NoExtractableText);
+    }
+
+    let pages_of = |document_type: DocumentType| {
+        verified_content
+            .pages
+            .iter()
+            .filter(|page| detect_document_type(page) == document_type)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let gst_text = pages_of(DocumentType::Gst);
+    let pan_text = pages_of(DocumentType::Pan);
+
+    let gst = extract_gst_certificate(
+        &gst_text,
+        verified_content.signature.clone(),
+        certificate.as_deref(),
+        &CertificateVerifier::default(),
+    )
+    .map(|extraction| with_annexure(extraction, &verified_content.pages))?
+    .certificate;
+    let pan = extract_pan_certificate(
+        &pan_text,
+        verified_content.signature,
+        certificate.as_deref(),
+        &CertificateVerifier::default(),
+    )?;
+
+    if !gstin_matches_pan(&gst.gst_number, &pan.pan_number) {
+        return Err(DocumentVerificationError::PanMismatch {
+            gstin: gst.gst_number,
+            pan: pan.pan_number,
+        });
+    }
+
+    Ok((gst, pan))
+}
+
+/// Outcome of `same_entity`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityMatch {
+    /// The GSTIN embeds the PAN and the names are compatible
+    Match,
+    /// The names are compatible but the GSTIN embeds another PAN
+    PanMismatch,
+    /// The GSTIN embeds the PAN but the names are not compatible
+    NameMismatch,
+    /// Neither the PAN nor the names match
+    Both,
+}
+
+impl EntityMatch {
+    /// Whether both the PAN and the names match
+    pub fn is_match(self) -> bool {
+        self == EntityMatch::Match
+    }
+}
+
+/// Whether a GST certificate and a PAN card plausibly belong to the same entity.
+///
+/// The PAN embedded in the GSTIN must equal the PAN number, and the legal names must share
+/// a `name_variants` form, so "ACME TRADERS PVT LTD" matches "Acme Traders Private
+/// Limited" only through the suffix-free "ACME TRADERS". Both certificates are assumed
+/// verified already; this only compares their extracted fields.
+pub fn same_entity(gst: &GSTCertificate, pan: &PANCertificate) -> EntityMatch {
+    let pan_matches = gstin_matches_pan(&gst.gst_number, &pan.pan_number);
+    let pan_variants = name_variants(&pan.legal_name);
+    let names_match = name_variants(&gst.legal_name)
+        .iter()
+        .any(|variant| pan_variants.contains(variant));
+
+    match (pan_matches, names_match) {
+        (true, true) => EntityMatch::Match,
+        (false, true) => EntityMatch::PanMismatch,
+        (true, false) => EntityMatch::NameMismatch,
+        (false, false) => EntityMatch::Both,
+    }
+}
+
+/// Certificate verifier with configurable extraction patterns.
+///
+/// The default verifier behaves exactly like `verify_certificate`; use
+/// `CertificateVerifier::builder()` to adapt it to other portal layouts.
+#[derive(Debug, Clone)]
+pub struct CertificateVerifier {
+    legal_name_regex: Option<Regex>,
+    trade_name_regex: Option<Regex>,
+    pan_name_regex: Option<Regex>,
+    validate_checksum: bool,
+    fuzzy: bool,
+    accept_special_gstins: bool,
+    extract_contact: bool,
+    document_type: Option<DocumentType>,
+    trusted_issuers: Option<Vec<[u8; 32]>>,
+    signer_valid_on: Option<NaiveDate>,
+    reject_weak_digests: bool,
+    reject_self_signed: bool,
+    max_scan_chars: usize,
+}
+
+impl Default for CertificateVerifier {
+    fn default() -> Self {
+        Self {
+            legal_name_regex: None,
+            trade_name_regex: None,
+            pan_name_regex: None,
+            validate_checksum: true,
+            fuzzy: false,
+            accept_special_gstins: false,
+            extract_contact: false,
+            document_type: None,
+            trusted_issuers: None,
+            signer_valid_on: None,
+            reject_weak_digests: false,
+            reject_self_signed: false,
+            max_scan_chars: DEFAULT_MAX_SCAN_CHARS,
+        }
+    }
+}
+
+impl CertificateVerifier {
+    /// Starts a builder from the default configuration
+    pub fn builder() -> CertificateVerifierBuilder {
+        CertificateVerifierBuilder::default()
+    }
+
+    /// Verifies the PDF and extracts the fields of the configured or detected document type
+    pub fn verify(
+        &self,
+        pdf_bytes: Vec<u8>,
+    ) -> Result<CertificateOutcome, DocumentVerificationError> {
+        self.verify_document(pdf_bytes)
+            .map(|document| document.certificate)
+    }
+
+    /// Like `verify`, also committing to the full text of the verified pages
+    pub fn verify_document(
+        &self,
+        pdf_bytes: Vec<u8>,
+    ) -> Result<VerifiedDocument, DocumentVerificationError> {
+        cycle_tracker!("start", "pdf-verification");
+        let verified = verify_signed_pdf(pdf_bytes);
+        cycle_tracker!("end", "pdf-verification");
+
+        let (verified_content, certificate) = verified?;
+        self.document_from_signed(verified_content, certificate.as_deref())
+    }
+
+    /// Like `verify_document`, for content the caller already got from zkpdf's
+    /// `verify_and_extract`; skips the size, signed byte range and signature checks, so
+    /// `verified_content` must be the unmodified result of verifying the PDF.
+    pub fn document_from_verified(
+        &self,
+        verified_content: PdfVerifiedContent,
+    ) -> Result<VerifiedDocument, DocumentVerificationError> {
+        self.document_from_signed(verified_content, None)
+    }
+
+    /// `document_from_verified` with the signer certificate from `utils::signer_certificate`
+    pub(crate) fn document_from_signed(
+        &self,
+        verified_content: PdfVerifiedContent,
+        certificate: Option<&[u8]>,
+    ) -> Result<VerifiedDocument, DocumentVerificationError> {
+        if !has_extractable_text(&verified_content.pages) {
+            return Err(DocumentVerificationError::NoExtractableText);
+        }
+
+        if let Some(as_of) = self.signer_valid_on {
+            let (not_before, not_after) = certificate
+                .and_then(signer_cert_validity)
+                .ok_or(DocumentVerificationError::SignerValidityUnknown)?;
+            if as_of < not_before || as_of > not_after {
+                return Err(DocumentVerificationError::SignerCertificateExpired {
+                    not_before,
+                    not_after,
+                    as_of,
+                });
+            }
+        }
+
+        let digest = digest_algorithm(&verified_content.signature);
+        if self.reject_weak_digests && digest.is_weak() {
+            return Err(DocumentVerificationError::WeakDigest(digest));
+        }
+        if self.reject_self_signed {
+            let certificate =
+                certificate.ok_or(DocumentVerificationError::SignerCertificateUnavailable)?;
+            if signer_is_self_signed(certificate) {
+                return Err(DocumentVerificationError::SelfSignedSigner);
+            }
+        }
+        if let Some(allowlist) = &self.trusted_issuers {
+            let public_key_hash: [u8; 32] =
+                keccak256(&verified_content.signature.public_key).into();
+            if !is_trusted_issuer(&public_key_hash, allowlist) {
+                return Err(DocumentVerificationError::UntrustedIssuer);
+            }
+        }
+
+        cycle_tracker!("start", "field-extraction");
+        let document_commitment =
+            full_document_commitment(&verified_content.pages, &verified_content.signature);
+        let full_text = verified_content.pages.join(" ");
+        // Detection and every extractor scan the same bounded prefix
+        let mut warnings = Vec::new();
+        let (full_text, truncated) = truncate_scan_text(&full_text, self.max_scan_chars);
+        if truncated {
+            warnings.push(ExtractionWarning::TextTruncated {
+                max_chars: self.max_scan_chars,
+            });
+        }
+        let signature = verified_content.signature;
+
+        // A PDF matching several types is rejected rather than read as whichever comes first
+        let document_type = match self.document_type {
+            Some(document_type) => document_type,
+            None => match detect_all_document_types(full_text).as_slice() {
+                [] => DocumentType::Unknown,
+                [document_type] => *document_type,
+                several => {
+                    return Err(DocumentVerificationError::AmbiguousDocumentType(
+                        several.to_vec(),
+                    ))
+                }
+            },
+        };
+
+        let certificate = match document_type {
+            DocumentType::Gst => extract_gst_certificate_with_warnings(
+                full_text,
+                signature,
+                certificate,
+                self,
+                false,
+                &mut warnings,
+            )
+            .map(|extraction| with_annexure(extraction, &verified_content.pages))
+            .map(|extraction| CertificateOutcome::Gst(extraction.certificate))
+            .map_err(DocumentVerificationError::from),
+            DocumentType::Pan => extract_pan_certificate(full_text, signature, certificate, self)
+                .map(CertificateOutcome::Pan)
+                .map_err(DocumentVerificationError::from),
+            DocumentType::DrivingLicense => {
+                extract_driving_license(full_text, signature, certificate)
+                    .map(CertificateOutcome::Dl)
+                    .map_err(DocumentVerificationError::from)
+            }
+            DocumentType::Aadhaar => extract_aadhaar_certificate(full_text, signature, certificate)
+                .map(CertificateOutcome::Aadhaar)
+                .map_err(DocumentVerificationError::from),
+            DocumentType::Unknown => Err(DocumentVerificationError::NoDocumentMatched),
+        };
+        cycle_tracker!("end", "field-extraction");
+
+        certificate.map(|certificate| VerifiedDocument {
+            certificate,
+            document_commitment,
+            warnings,
+        })
+    }
+}
+
+/// Builder for `CertificateVerifier`
+#[derive(Debug, Clone, Default)]
+pub struct CertificateVerifierBuilder {
+    verifier: CertificateVerifier,
+}
+
+impl CertificateVerifierBuilder {
+    /// Overrides the GST legal name pattern; capture group 1 must hold the name
+    pub fn legal_name_pattern(mut self, regex: Regex) -> Self {
+        self.verifier.legal_name_regex = Some(regex);
+        self
+    }
+
+    /// Overrides the trade name pattern; capture group 1 must hold the name, and the
+    /// legal name is cut where the pattern matches
+    pub fn trade_name_pattern(mut self, regex: Regex) -> Self {
+        self.verifier.trade_name_regex = Some(regex);
+        self
+    }
+
+    /// Ends the GST legal name at `config.stop_labels`, replacing any `legal_name_pattern`
+    pub fn gst_config(mut self, config: &GstConfig) -> Self {
+        self.verifier.legal_name_regex = Some(config.legal_name_regex());
+        self
+    }
+
+    /// Ends the PAN holder's and father's names at `config.stop_labels`
+    pub fn pan_config(mut self, config: &PanConfig) -> Self {
+        self.verifier.pan_name_regex = Some(config.name_value_regex());
+        self
+    }
+
+    /// Enables or disables GSTIN checksum validation (enabled by default)
+    pub fn validate_checksum(mut self, validate: bool) -> Self {
+        self.verifier.validate_checksum = validate;
+        self
+    }
+
+    /// Also accepts GSTINs printed with `O`/`I` in place of `0`/`1` where the format requires
+    /// digits, if the corrected GSTIN's checksum is valid (disabled by default)
+    pub fn fuzzy(mut self, fuzzy: bool) -> Self {
+        self.verifier.fuzzy = fuzzy;
+        self
+    }
+
+    /// Also accepts UIN, TDS and OIDAR registrations, which don't embed a PAN; they are
+    /// otherwise only reported as `ExtractionWarning::SpecialGstin` (disabled by default)
+    pub fn accept_special_gstins(mut self, accept: bool) -> Self {
+        self.verifier.accept_special_gstins = accept;
+        self
+    }
+
+    /// Reads the GST contact email and the last four digits of the mobile number, for
+    /// off-chain notification flows (disabled by default).
+    ///
+    /// Contact details are never committed or added to the public values, whatever the
+    /// `DisclosureMask`.
+    pub fn extract_contact(mut self, extract_contact: bool) -> Self {
+        self.verifier.extract_contact = extract_contact;
+        self
+    }
+
+    /// Skips detection and always extracts the given document type
+    pub fn document_type(mut self, document_type: DocumentType) -> Self {
+        self.verifier.document_type = Some(document_type);
+        self
+    }
+
+    /// Rejects documents of every kind whose signer key hash is not in the allowlist,
+    /// e.g. `utils::KNOWN_ISSUER_KEY_HASHES`. A kind with no key in the allowlist is
+    /// always rejected.
+    pub fn trusted_issuers(mut self, allowlist: Vec<[u8; 32]>) -> Self {
+        self.verifier.trusted_issuers = Some(allowlist);
+        self
+    }
+
+    /// Rejects PDFs whose signer certificate is not valid on `as_of`, including PDFs that
+    /// do not carry the signer certificate and so have no readable validity window
+    pub fn reject_if_signer_expired(mut self, as_of: NaiveDate) -> Self {
+        self.verifier.signer_valid_on = Some(as_of);
+        self
+    }
+
+    /// Rejects PDFs signed over an MD5 or SHA-1 digest (disabled by default).
+    ///
+    /// Like the other signer checks, `reject_self_signed` and `reject_if_signer_expired`,
+    /// this applies to this verifier's `verify`, `verify_document` and
+    /// `document_from_verified` only; the `verify_*_certificate` functions run none of
+    /// these checks.
+    pub fn reject_weak_digests(mut self, reject: bool) -> Self {
+        self.verifier.reject_weak_digests = reject;
+        self
+    }
+
+    /// Rejects PDFs whose signer certificate is self-signed, since anyone can make one,
+    /// and PDFs that do not carry the signer certificate to check (disabled by default).
+    /// See `utils::signer_is_self_signed` for how much the check proves without the
+    /// `embedded-issuers` feature.
+    pub fn reject_self_signed(mut self, reject: bool) -> Self {
+        self.verifier.reject_self_signed = reject;
+        self
+    }
+
+    /// Scans at most `max_chars` characters of the joined page text for fields, to bound
+    /// the worst-case regex cost (`DEFAULT_MAX_SCAN_CHARS` by default). The document
+    /// commitment still covers the full text.
+    pub fn max_scan_chars(mut self, max_chars: usize) -> Self {
+        self.verifier.max_scan_chars = max_chars;
+        self
+    }
+
+    pub fn build(self) -> CertificateVerifier {
+        self.verifier
+    }
+}
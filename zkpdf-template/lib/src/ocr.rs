@@ -0,0 +1,43 @@
+//! OCR of image-only PDF pages, for scans whose text layer is empty (off-chain only)
+//!
+//! Pages are rendered with PDFium and read with Tesseract, so both libraries must be
+//! installed. Neither runs in the zkVM: OCR output is not deterministic across library
+//! versions and could never be bound to the signed bytes anyway.
+
+use pdfium_render::prelude::{PdfRenderConfig, Pdfium};
+use tesseract::Tesseract;
+
+/// Rendering width of a page; about 300 dpi for an A4 page, which Tesseract reads best
+const RENDER_WIDTH: i32 = 2480;
+
+/// Tesseract language the certificates are printed in
+const OCR_LANGUAGE: &str = "eng";
+
+/// Text of each page of `pdf_bytes`, rendered and recognized by OCR
+pub(crate) fn ocr_pages(pdf_bytes: &[u8]) -> Result<Vec<String>, String> {
+    let bindings = Pdfium::bind_to_system_library().map_err(|e| e.to_string())?;
+    let pdfium = Pdfium::new(bindings);
+    let document = pdfium
+        .load_pdf_from_byte_slice(pdf_bytes, None)
+        .map_err(|e| e.to_string())?;
+    let config = PdfRenderConfig::new().set_target_width(RENDER_WIDTH);
+
+    document
+        .pages()
+        .iter()
+        .map(|page| {
+            let bitmap = page
+                .render_with_config(&config)
+                .map_err(|e| e.to_string())?;
+            let (width, height) = (bitmap.width(), bitmap.height());
+            Tesseract::new(None, Some(OCR_LANGUAGE))
+                .map_err(|e| e.to_string())?
+                .set_frame(&bitmap.as_rgba_bytes(), width, height, 4, width * 4)
+                .map_err(|e| e.to_string())?
+                .recognize()
+                .map_err(|e| e.to_string())?
+                .get_text()
+                .map_err(|e| e.to_string())
+        })
+        .collect()
+}
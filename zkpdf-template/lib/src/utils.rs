@@ -4,95 +4,4926 @@
 //! and error handling for GST certificate verification.
 
 use alloy_primitives::keccak256;
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
 
+use zkpdf_lib::PdfSignatureResult;
+
+use crate::AadhaarCertificate;
+use crate::DLCertificate;
+use crate::DocumentType;
 use crate::GSTCertificate;
 use crate::PANCertificate;
 
-/// Generate a commitment hash from the GST certificate data
-pub fn gst_generate_commitment(gst: &GSTCertificate) -> [u8; 32] {
-    let mut combined_input = Vec::new();
-    combined_input.extend_from_slice(&gst.signature.message_digest);
-    combined_input.extend_from_slice(gst.gst_number.as_bytes());
-    combined_input.extend_from_slice(gst.legal_name.as_bytes());
-    combined_input.extend_from_slice(&gst.signature.public_key);
+/// GSTIN: 2 digit state code + PAN + entity number + 'Z' + checksum
+pub(crate) static GST_NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([0-9]{2}[A-Z]{5}[0-9]{4}[A-Z]{1}[1-9A-Z]{1}[Z]{1}[0-9A-Z]{1})").unwrap()
+});
+
+/// GSTIN shape that also allows `O` and `I` where `GST_NUMBER_REGEX` requires digits
+pub(crate) static GST_NUMBER_CONFUSABLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([0-9OI]{2}[A-Z]{5}[0-9OI]{4}[A-Z]{1}[1-9A-Z]{1}[Z]{1}[0-9A-Z]{1})").unwrap()
+});
+
+/// GSTINs of special taxpayer types, which don't embed a PAN: UIN holders (`UN`), TDS
+/// deductors (a TAN and `D`) and non-resident OIDAR suppliers (`OS`)
+pub(crate) static GST_SPECIAL_NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"([0-9]{4}[A-Z]{3}[0-9]{5}UN[0-9A-Z]|[0-9]{2}[A-Z]{4}[0-9]{5}[A-Z][1-9A-Z]D[0-9A-Z]|99[0-9]{2}[A-Z]{3}[0-9]{5}OS[0-9A-Z])",
+    )
+    .unwrap()
+});
+
+/// A whole GSTIN, with one named group per `GstinCategory`
+static GSTIN_CATEGORY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"^(?:(?P<standard>[0-9]{2}[A-Z]{5}[0-9]{4}[A-Z][1-9A-Z]Z[0-9A-Z])",
+        r"|(?P<uin>[0-9]{4}[A-Z]{3}[0-9]{5}UN[0-9A-Z])",
+        r"|(?P<tds>[0-9]{2}[A-Z]{4}[0-9]{5}[A-Z][1-9A-Z]D[0-9A-Z])",
+        r"|(?P<oidar>99[0-9]{2}[A-Z]{3}[0-9]{5}OS[0-9A-Z]))$",
+    ))
+    .unwrap()
+});
+
+/// Legal name, up to the next line or one of `GstConfig::default().stop_labels`
+pub(crate) static GST_LEGAL_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| GstConfig::default().legal_name_regex());
+
+/// Trade name, printed as "Trade Name, if any" on the registration certificate
+pub(crate) static GST_TRADE_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"Trade Name(?:,\s*if any)?\s*:?\s*([A-Za-z\s&.,]+?)(?:\n|Additional|Constitution|$)",
+    )
+    .unwrap()
+});
+
+/// Date the registration took effect ("Date of Liability" / "Period of Validity From")
+pub(crate) static GST_REGISTRATION_DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:Date of Liability|Period of Validity\s*From|Date of Registration)\s*:?\s*(\d{2}/\d{2}/\d{4}|\d{2}-\d{2}-\d{4}|\d{4}-\d{2}-\d{2})",
+    )
+    .unwrap()
+});
+
+/// "Constitution of Business", e.g. "Proprietorship" or "Private Limited Company"
+pub(crate) static GST_CONSTITUTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Constitution of Business\s*:?\s*([A-Za-z\s()&./\-]+?)(?:\n|Address|Principal|$)")
+        .unwrap()
+});
+
+/// "Type of Registration", printed near the date of liability, e.g. "Regular"
+pub(crate) static GST_REGISTRATION_TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Type of Registration\s*:?\s*([A-Za-z\s\-]+?)(?:\n|Particulars|$)").unwrap()
+});
+
+/// Contact email on some GST annexures, possibly masked like `ab****@example.com`
+pub(crate) static GST_EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)E-?mail(?:\s*(?:Id|Address))?\s*:?\s*([A-Za-z0-9._%+*-]+@[A-Za-z0-9*-]+(?:\.[A-Za-z0-9*-]+)*\.[A-Za-z]{2,})",
+    )
+    .unwrap()
+});
+
+/// Contact mobile number on some GST annexures; only the last four digits are captured
+pub(crate) static GST_MOBILE_LAST4_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)Mobile(?:\s*(?:No\.?|Number))?\s*:?\s*(?:\+?91[\s-]?)?[0-9Xx*]{6}([0-9]{4})\b")
+        .unwrap()
+});
 
-    keccak256(&combined_input).as_slice().try_into().unwrap()
+/// "Address of Principal Place of Business" block, running over several lines up to the
+/// next numbered section or field header
+pub(crate) static GST_ADDRESS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?s)(?:Address of\s+)?Principal Place of\s+Business\s*:?\s*(.+?)\s*(?:\n\s*\d{1,2}\.\s|Date of Liability|Period of Validity|Type of Registration|Particulars of Approving|\z)",
+    )
+    .unwrap()
+});
+
+/// "Annexure A" or "Annexure B" heading at the start of a line of a REG-06 certificate
+pub(crate) static GST_ANNEXURE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?im)^[ \t]*Annexure[ \t]*[-:]?[ \t]*([AB])\b").unwrap());
+
+/// Serial number opening an annexure table row, alone or followed by the row's first line
+pub(crate) static GST_ANNEXURE_SERIAL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(\d{1,3})\.?(?:\s+(.*))?$").unwrap());
+
+/// Lines after the annexure table: notes, page numbers and the signature stamp
+pub(crate) static GST_ANNEXURE_END_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^\s*(?:note\b|page\s+\d+|digitally signed|signature)").unwrap());
+
+/// Name of a partner, director or karta in Annexure B, on the "Name" line or the next one
+pub(crate) static GST_ANNEXURE_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:\d{1,3}\.?[ \t]+)?Name[ \t]*:?[ \t]*(?:\n[ \t]*)?(\S[^\n]*)$")
+        .unwrap()
+});
+
+/// PAN: 5 letters + 4 digits + 1 letter
+pub(crate) static PAN_NUMBER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([A-Z]{5}[0-9]{4}[A-Z]{1})").unwrap());
+
+/// "Name" label on the PAN card; group 1 is set when it labels the father's name
+pub(crate) static PAN_NAME_LABEL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:(Father(?:'s|’s|s)?)\s*)?\bName\b\s*:?").unwrap());
+
+/// Name following a PAN name label, up to the next line or one of
+/// `PanConfig::default().stop_labels`
+pub(crate) static PAN_NAME_VALUE_REGEX: Lazy<Regex> =
+    Lazy::new(|| PanConfig::default().name_value_regex());
+
+/// Labels ending the GST legal name when the next field follows on the same line
+pub const DEFAULT_GST_STOP_LABELS: &[&str] = &["Trade Name", "Additional"];
+
+/// Labels ending a PAN name when the next field follows on the same line
+pub const DEFAULT_PAN_STOP_LABELS: &[&str] = &["Father", "DOB", "Date of Birth"];
+
+/// Characters of a name printed in English only
+const LATIN_NAME_CHARS: &str = r"[A-Za-z\s&.,]";
+
+/// Characters of a name printed in English and a regional script, e.g. "ACME / एक्मे".
+/// Indic vowel signs are marks (`\p{M}`), not letters, so both classes are needed.
+const BILINGUAL_NAME_CHARS: &str = r"[\p{L}\p{M}\s&.,/]";
+
+/// Name pattern: `prefix`, then a run of `name_chars` in group 1, up to a newline, any
+/// of `stop_labels` (matched literally) or the end of the text
+fn name_regex(prefix: &str, name_chars: &str, stop_labels: &[String]) -> Regex {
+    let stops: String = stop_labels
+        .iter()
+        .map(|label| format!("{}|", regex::escape(label)))
+        .collect();
+
+    Regex::new(&format!(r"{}({}+?)(?:\n|{}$)", prefix, name_chars, stops))
+        .expect("escaped stop labels always form a valid pattern")
 }
 
-/// GST
-#[derive(Debug)]
-pub enum GSTVerificationError {
-    PdfVerificationFailed(String),
-    RegexCompilationFailed(String),
-    GSTNumberNotFound,
-    LegalNameNotFound,
+/// GST extraction settings for portal layouts the defaults do not cover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GstConfig {
+    /// Labels the legal name stops at; append portal-specific ones such as "GSTIN"
+    pub stop_labels: Vec<String>,
+}
+
+impl Default for GstConfig {
+    fn default() -> Self {
+        Self {
+            stop_labels: DEFAULT_GST_STOP_LABELS
+                .iter()
+                .map(|label| label.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl GstConfig {
+    /// Legal name pattern ending at any of `stop_labels`, accepting regional scripts
+    /// alongside English; see `normalize_bilingual_name`
+    pub fn legal_name_regex(&self) -> Regex {
+        name_regex(r"Legal Name\s*", BILINGUAL_NAME_CHARS, &self.stop_labels)
+    }
+}
+
+/// PAN extraction settings for card layouts the defaults do not cover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanConfig {
+    /// Labels the holder's and father's names stop at; append layout-specific ones
+    pub stop_labels: Vec<String>,
+}
+
+impl Default for PanConfig {
+    fn default() -> Self {
+        Self {
+            stop_labels: DEFAULT_PAN_STOP_LABELS
+                .iter()
+                .map(|label| label.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl PanConfig {
+    /// Pattern for the name following a name label, ending at any of `stop_labels`
+    pub fn name_value_regex(&self) -> Regex {
+        name_regex(r"^\s*", LATIN_NAME_CHARS, &self.stop_labels)
+    }
+}
+
+/// Issue or print date on newer e-PAN PDFs
+pub(crate) static PAN_PRINT_DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:Date of Issue|Issue Date|Date of Print(?:ing)?|Print Date)\s*:?\s*(\d{2}/\d{2}/\d{4}|\d{2}-\d{2}-\d{4}|\d{4}-\d{2}-\d{2})",
+    )
+    .unwrap()
+});
+
+/// DOB, usually in DD/MM/YYYY or DD-MM-YYYY format on PAN card
+pub(crate) static PAN_DOB_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d{2}/\d{2}/\d{4}|\d{2}-\d{2}-\d{4})").unwrap());
+
+/// DL number: state code + RTO code + year of issue + 7 digit serial (SS-RR-YYYY-NNNNNNN)
+pub(crate) static DL_NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([A-Z]{2}[-\s]?[0-9]{2}[-\s]?(?:19|20)[0-9]{2}[-\s]?[0-9]{7})").unwrap()
+});
+
+pub(crate) static DL_HOLDER_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Name\s*:?\s*([A-Za-z\s.]+?)(?:\n|S/D/W|Son|Daughter|Wife|DOB|Date of Birth|$)")
+        .unwrap()
+});
+
+pub(crate) static DL_ISSUING_AUTHORITY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Issuing Authority\s*:?\s*([A-Za-z0-9\s,.\-]+?)(?:\n|$)").unwrap());
+
+/// Class of vehicle, e.g. "LMV", "MCWG" or "LMV, MCWG"
+pub(crate) static DL_LICENSE_TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:Class of Vehicle|COV)\s*:?\s*([A-Z][A-Z0-9\-]*(?:\s*,\s*[A-Z][A-Z0-9\-]*)*)")
+        .unwrap()
+});
+
+pub(crate) static DL_EXPIRY_DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:Valid Till|Valid Upto|Validity\s*\(NT\)|Expiry Date)\s*:?\s*(\d{2}[/-]\d{2}[/-]\d{4})",
+    )
+    .unwrap()
+});
+
+/// Masked Aadhaar number as printed on e-Aadhaar ("XXXX XXXX 1234"); only the last four
+/// digits are captured
+pub(crate) static AADHAAR_MASKED_UID_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b[Xx*]{4}[\s-]?[Xx*]{4}[\s-]?(\d{4})\b").unwrap());
+
+/// Holder name, printed on the line above the date of birth
+pub(crate) static AADHAAR_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([A-Za-z][A-Za-z .]*[A-Za-z.])[ \t]*\n\s*(?:DOB|Date of Birth|Year of Birth)")
+        .unwrap()
+});
+
+/// Date of birth, or only the year for older enrolments
+pub(crate) static AADHAAR_DOB_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:DOB|Date of Birth|Year of Birth)\s*:?\s*(\d{2}/\d{2}/\d{4}|\d{4})").unwrap()
+});
+
+/// "Gender" label on the PAN card, when the layout prints one
+pub(crate) static PAN_GENDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:Gender|Sex)\s*[:/]?\s*(female|male|transgender|other|f|m|t)\b").unwrap()
+});
+
+/// Gender as printed next to the date of birth
+pub(crate) static GENDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(female|male|transgender|other)\b").unwrap());
+
+/// Largest PDF the verifiers accept, bounding the zkVM cycles spent parsing it.
+///
+/// Fixed inside the program; off-chain scripts may pre-check against a different limit,
+/// see `max_pdf_bytes_from_env`.
+pub const MAX_PDF_BYTES: usize = 10 * 1024 * 1024;
+
+/// Default cap on the characters of joined page text scanned by the field regexes.
+///
+/// Real certificates are a few thousand characters; the cap only bounds the regex cost,
+/// and so the proving cycles, of a PDF stuffed with repeated text.
+pub const DEFAULT_MAX_SCAN_CHARS: usize = 1 << 20;
+
+/// The first `max_chars` characters of `text`, and whether anything was cut off
+pub fn truncate_scan_text(text: &str, max_chars: usize) -> (&str, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => (&text[..end], true),
+        None => (text, false),
+    }
+}
+
+/// Environment variable overriding `MAX_PDF_BYTES` in the off-chain scripts
+pub const MAX_PDF_BYTES_ENV: &str = "ZKPDF_MAX_PDF_BYTES";
+
+/// Off-chain PDF size limit: `ZKPDF_MAX_PDF_BYTES` when set to a number, otherwise
+/// `MAX_PDF_BYTES`. Never used by the program, whose limit must stay deterministic.
+pub fn max_pdf_bytes_from_env() -> usize {
+    std::env::var(MAX_PDF_BYTES_ENV)
+        .ok()
+        .and_then(|limit| limit.trim().parse().ok())
+        .unwrap_or(MAX_PDF_BYTES)
+}
+
+/// Why the signature of a PDF does not cover every byte its text could be read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TamperError {
+    ByteRangeNotFound,
+    MalformedByteRange,
+    /// Bytes after the signed range, e.g. an incremental update appended after signing
+    UnsignedTrailingBytes {
+        signed_end: usize,
+        file_len: usize,
+    },
+    /// The excluded gap holds more than the hex-encoded signature
+    UnsignedGap,
+    /// More than one signature dictionary, e.g. one appended in an incremental update.
+    /// zkpdf verifies a single signature without saying which, so the checked range
+    /// could not be tied to the verified one.
+    MultipleSignatures(usize),
 }
 
-impl fmt::Display for GSTVerificationError {
+impl fmt::Display for TamperError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            GSTVerificationError::PdfVerificationFailed(msg) => {
-                write!(f, "PDF verification failed: {}", msg)
-            }
-            GSTVerificationError::RegexCompilationFailed(msg) => {
-                write!(f, "Regex compilation failed: {}", msg)
-            }
-            GSTVerificationError::GSTNumberNotFound => {
-                write!(f, "GST number not found in PDF")
+            TamperError::ByteRangeNotFound => write!(f, "PDF has no signature ByteRange"),
+            TamperError::MalformedByteRange => write!(f, "PDF signature ByteRange is malformed"),
+            TamperError::UnsignedTrailingBytes {
+                signed_end,
+                file_len,
+            } => write!(
+                f,
+                "Signature covers {} of {} bytes; content was appended after signing",
+                signed_end, file_len
+            ),
+            TamperError::UnsignedGap => {
+                write!(
+                    f,
+                    "Bytes excluded from the signature are not only the signature"
+                )
             }
-            GSTVerificationError::LegalNameNotFound => {
-                write!(f, "Legal name not found in PDF")
+            TamperError::MultipleSignatures(count) => {
+                write!(f, "PDF has {} signatures; only one is supported", count)
             }
         }
     }
 }
 
-impl Error for GSTVerificationError {}
+impl Error for TamperError {}
 
-/// Generate a commitment hash from the PAN certificate data
-pub fn pan_generate_commitment(pan: &PANCertificate) -> [u8; 32] {
-    let mut combined_input = Vec::new();
-    combined_input.extend_from_slice(&pan.signature.message_digest);
-    combined_input.extend_from_slice(pan.pan_number.as_bytes());
-    combined_input.extend_from_slice(pan.legal_name.as_bytes());
-    combined_input.extend_from_slice(&pan.signature.public_key);
+/// Why a PDF failed the checks every verifier runs before extracting fields; each
+/// document type's error converts from it
+#[derive(Debug)]
+pub enum SignedPdfError {
+    PdfTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    /// The PDF is encrypted. zkpdf checks the signature over the raw file bytes and reads
+    /// the text from them too, with no hook to decrypt streams first, and rewriting the
+    /// file decrypted would break the signed byte range.
+    Encrypted,
+    Tampered(TamperError),
+    /// zkpdf's `verify_and_extract` failed
+    VerificationFailed(String),
+}
+
+/// Whether the trailer points at an `/Encrypt` dictionary, i.e. the PDF's strings and
+/// streams are encrypted under the standard or another security handler
+pub fn is_encrypted_pdf(pdf_bytes: &[u8]) -> bool {
+    const KEY: &[u8] = b"/Encrypt";
 
-    keccak256(&combined_input).as_slice().try_into().unwrap()
+    let mut rest = pdf_bytes;
+    while let Some(start) = rest.windows(KEY.len()).position(|window| window == KEY) {
+        rest = &rest[start + KEY.len()..];
+        // `/Encrypt 5 0 R` or an inline `/Encrypt << ... >>`, not `/EncryptMetadata`
+        let value = rest.iter().find(|b| !b.is_ascii_whitespace());
+        if rest.first().is_some_and(|b| !b.is_ascii_alphanumeric())
+            && value.is_some_and(|&b| b.is_ascii_digit() || b == b'<')
+        {
+            return true;
+        }
+    }
+    false
 }
 
-/// PAN
-#[derive(Debug)]
-pub enum PANVerificationError {
-    PdfVerificationFailed(String),
-    RegexCompilationFailed(String),
-    PANNumberNotFound,
-    LegalNameNotFound,
-    DOBNotFound,
+/// Parses the four integers of every `/ByteRange [a b c d]` entry in the file
+fn signature_byte_ranges(pdf_bytes: &[u8]) -> Result<Vec<[usize; 4]>, TamperError> {
+    const KEY: &[u8] = b"/ByteRange";
+
+    let mut ranges = Vec::new();
+    let mut rest = pdf_bytes;
+    while let Some(start) = rest.windows(KEY.len()).position(|window| window == KEY) {
+        rest = &rest[start + KEY.len()..];
+        let open = rest
+            .iter()
+            .position(|&b| !b.is_ascii_whitespace())
+            .filter(|&i| rest[i] == b'[')
+            .ok_or(TamperError::MalformedByteRange)?;
+        let close = rest
+            .iter()
+            .position(|&b| b == b']')
+            .ok_or(TamperError::MalformedByteRange)?;
+
+        let values = std::str::from_utf8(&rest[open + 1..close])
+            .map_err(|_| TamperError::MalformedByteRange)?
+            .split_ascii_whitespace()
+            .map(|value| value.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| TamperError::MalformedByteRange)?;
+        ranges.push(
+            values
+                .try_into()
+                .map_err(|_| TamperError::MalformedByteRange)?,
+        );
+        rest = &rest[close..];
+    }
+
+    Ok(ranges)
+}
+
+/// Confirms the signature covers every byte of the PDF except its own hex-encoded value.
+///
+/// The text layer is read from the whole file, while the signature only covers its
+/// `/ByteRange`. Requiring the range to start at byte 0, end at the last byte, and
+/// exclude nothing but the `<...>` signature value means any text the extractor can read
+/// was signed, so a text layer appended or spliced in after signing is rejected.
+///
+/// The PDF must have exactly one `/ByteRange`, so the range checked here is the one of
+/// the signature `verify_and_extract` verifies. Otherwise an incremental update could
+/// append a forged signature dictionary whose range covers the whole file, passing this
+/// check while zkpdf verifies the original signature over the original bytes. It does
+/// not check that the signature itself is valid; that is `verify_and_extract`'s job.
+pub fn assert_text_within_signed_range(pdf_bytes: &[u8]) -> Result<(), TamperError> {
+    let [start, gap_start, gap_end, tail_len] = match signature_byte_ranges(pdf_bytes)?[..] {
+        [] => return Err(TamperError::ByteRangeNotFound),
+        [range] => range,
+        ref ranges => return Err(TamperError::MultipleSignatures(ranges.len())),
+    };
+
+    let signed_end = gap_end
+        .checked_add(tail_len)
+        .ok_or(TamperError::MalformedByteRange)?;
+    if start != 0 || gap_start > gap_end || signed_end > pdf_bytes.len() {
+        return Err(TamperError::MalformedByteRange);
+    }
+    if signed_end != pdf_bytes.len() {
+        return Err(TamperError::UnsignedTrailingBytes {
+            signed_end,
+            file_len: pdf_bytes.len(),
+        });
+    }
+
+    let gap = &pdf_bytes[gap_start..gap_end];
+    let is_signature_value = gap.first() == Some(&b'<')
+        && gap.last() == Some(&b'>')
+        && gap[1..gap.len() - 1]
+            .iter()
+            .all(|b| b.is_ascii_hexdigit() || b.is_ascii_whitespace());
+    if !is_signature_value {
+        return Err(TamperError::UnsignedGap);
+    }
+
+    Ok(())
+}
+
+/// Whether a `verify_and_extract` error reports a signature that does not verify, rather
+/// than a PDF that could not be parsed.
+///
+/// zkpdf reports both as plain strings, so this matches on the message.
+pub fn is_signature_failure(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    let mismatch = ["invalid", "mismatch", "does not match"]
+        .iter()
+        .any(|word| message.contains(word));
+    (message.contains("signature") || message.contains("digest")) && mismatch
+}
+
+/// Whether a `verify_and_extract` error reports a PDF that could not be parsed, which a
+/// retry with a fresh download may fix.
+///
+/// Messages neither this nor `is_signature_failure` recognise are not assumed to be
+/// either, see `GSTVerificationError::VerificationFailed`.
+pub fn is_parse_failure(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    ["parse", "xref", "eof", "malformed", "trailer"]
+        .iter()
+        .any(|word| message.contains(word))
+}
+
+/// Whether any page holds text beyond whitespace. Image-only scans verify but yield
+/// nothing to extract, and need OCR rather than a different extraction pattern.
+pub fn has_extractable_text(pages: &[String]) -> bool {
+    pages.iter().any(|page| !page.trim().is_empty())
+}
+
+/// Domain separator prepended to every GST commitment preimage
+pub const GST_COMMITMENT_DOMAIN: &[u8] = b"zkverifid:gst:v1";
+
+/// Field tag preceding the address in a GST commitment preimage, so an address is never
+/// mistaken for the optional trade name before it
+pub const GST_ADDRESS_FIELD_TAG: &[u8] = b"address";
+
+/// Precedes the registration type in the GST commitment, as `GST_ADDRESS_FIELD_TAG` does
+/// for the address
+pub const GST_REGISTRATION_TYPE_FIELD_TAG: &[u8] = b"registration_type";
+
+/// Domain separator prepended to every PAN commitment preimage
+pub const PAN_COMMITMENT_DOMAIN: &[u8] = b"zkverifid:pan:v1";
+
+/// Domain separator prepended to every Driving License commitment preimage
+pub const DL_COMMITMENT_DOMAIN: &[u8] = b"zkverifid:dl:v1";
+
+/// Domain separator prepended to every full document commitment preimage
+pub const DOCUMENT_COMMITMENT_DOMAIN: &[u8] = b"zkverifid:document:v1";
+
+/// Domain separator prepended to every Aadhaar commitment preimage
+pub const AADHAAR_COMMITMENT_DOMAIN: &[u8] = b"zkverifid:aadhaar:v1";
+
+/// Domain separator of the per-field commitments published in place of hidden fields
+pub const FIELD_COMMITMENT_DOMAIN: &[u8] = b"zkverifid:field:v1";
+
+/// Preimage of a salted document commitment.
+///
+/// Serialized as `salt || len(domain) || domain || (len(field) || field)*` with
+/// big-endian `u32` lengths, so no two inputs share the same byte layout. Solidity
+/// verifiers can rebuild the preimage with `abi.encodePacked` over the same parts.
+pub struct CommitmentInput<'a> {
+    pub salt: [u8; 32],
+    pub domain: &'a [u8],
+    pub fields: Vec<&'a [u8]>,
+}
+
+/// Concatenate fields with a big-endian `u32` length before each one.
+///
+/// Adjacent fields can't shift bytes between each other, so `["27AA", "BC"]` and
+/// `["27A", "ABC"]` encode differently. Big-endian matches `abi.encodePacked(uint32)`.
+pub fn canonical_encode(fields: &[&[u8]]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for field in fields {
+        bytes.extend_from_slice(&(field.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(field);
+    }
+    bytes
+}
+
+impl CommitmentInput<'_> {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let parts: Vec<&[u8]> = std::iter::once(self.domain)
+            .chain(self.fields.iter().copied())
+            .collect();
+        [self.salt.as_slice(), &canonical_encode(&parts)].concat()
+    }
+
+    pub fn commitment(&self) -> [u8; 32] {
+        keccak256(self.to_bytes()).0
+    }
+}
+
+/// Salted commitment to a single field, published in the public values in place of the
+/// field's value, over `FIELD_COMMITMENT_DOMAIN`, `name` and `value`.
+///
+/// GSTINs and names are few enough to enumerate, so a bare keccak256 of the value could
+/// be brute forced, and would link every proof of the same holder.
+pub fn salted_field_commitment(salt: [u8; 32], name: &[u8], value: &[u8]) -> [u8; 32] {
+    CommitmentInput {
+        salt,
+        domain: FIELD_COMMITMENT_DOMAIN,
+        fields: vec![name, value],
+    }
+    .commitment()
 }
 
+/// Errors from generating a document commitment
+#[derive(Debug)]
+pub enum CommitmentError {
+    MissingPublicKey,
+    /// The fields were read by OCR, so the signature does not cover them
+    OcrText,
+}
 
-impl fmt::Display for PANVerificationError {
+impl fmt::Display for CommitmentError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            PANVerificationError::PdfVerificationFailed(msg) => {
-                write!(f, "PDF verification failed: {}", msg)
+            CommitmentError::MissingPublicKey => {
+                write!(f, "Signature is invalid or has no public key to commit to")
             }
-            PANVerificationError::RegexCompilationFailed(msg) => {
-                write!(f, "Regex compilation failed: {}", msg)
+            CommitmentError::OcrText => {
+                write!(
+                    f,
+                    "Fields were read by OCR and are not bound to the signature"
+                )
             }
-            PANVerificationError::PANNumberNotFound => {
-                write!(f, "PAN number not found in PDF")
+        }
+    }
+}
+
+impl Error for CommitmentError {}
+
+/// A certificate with a salted, domain-separated field commitment.
+///
+/// Every document type builds its commitment the same way, as a `CommitmentInput` over
+/// `DOMAIN` and `commitment_fields`; only the fields differ.
+pub trait Commitable {
+    /// Domain separator of this document type's commitment preimage
+    const DOMAIN: &'static [u8];
+
+    /// Committed fields in preimage order; fails for unsigned certificates
+    fn commitment_fields(&self) -> Result<Vec<Vec<u8>>, CommitmentError>;
+
+    fn commitment(&self, salt: [u8; 32]) -> Result<[u8; 32], CommitmentError> {
+        let fields = self.commitment_fields()?;
+        Ok(fields_commitment(salt, Self::DOMAIN, &fields))
+    }
+
+    /// Exact bytes keccak256'd into `commitment`, for comparing against a Solidity rebuild
+    fn commitment_preimage(&self, salt: [u8; 32]) -> Result<Vec<u8>, CommitmentError> {
+        let fields = self.commitment_fields()?;
+        Ok(fields_input(salt, Self::DOMAIN, &fields).to_bytes())
+    }
+}
+
+/// Zero-salt commitment identifying the signed document a certificate was parsed from, or
+/// `None` when the signature is invalid or carries no key
+pub(crate) fn document_identity<C: Commitable>(cert: &C) -> Option<[u8; 32]> {
+    cert.commitment([0u8; 32]).ok()
+}
+
+/// What certificates are compared and hashed by: the `document_identity`, or for
+/// certificates that cannot be committed, the document number and signed digest
+#[derive(PartialEq, Eq, Hash)]
+pub(crate) enum DocumentKey<'a> {
+    Committed([u8; 32]),
+    Uncommitted { number: &'a str, digest: &'a [u8] },
+}
+
+pub(crate) fn document_key<'a, C: Commitable>(
+    cert: &C,
+    number: &'a str,
+    digest: &'a [u8],
+) -> DocumentKey<'a> {
+    match document_identity(cert) {
+        Some(identity) => DocumentKey::Committed(identity),
+        None => DocumentKey::Uncommitted { number, digest },
+    }
+}
+
+fn fields_input<'a>(
+    salt: [u8; 32],
+    domain: &'a [u8],
+    fields: &'a [Vec<u8>],
+) -> CommitmentInput<'a> {
+    CommitmentInput {
+        salt,
+        domain,
+        fields: fields.iter().map(Vec::as_slice).collect(),
+    }
+}
+
+fn fields_commitment(salt: [u8; 32], domain: &[u8], fields: &[Vec<u8>]) -> [u8; 32] {
+    fields_input(salt, domain, fields).commitment()
+}
+
+/// `value` itself when revealed, otherwise its keccak256 hash
+fn reveal_or_hash(revealed: bool, value: &[u8]) -> Vec<u8> {
+    if revealed {
+        value.to_vec()
+    } else {
+        keccak256(value).to_vec()
+    }
+}
+
+/// Public key of a valid signature; unsigned PDFs have nothing meaningful to commit to
+fn signed_public_key(signature: &PdfSignatureResult) -> Result<&[u8], CommitmentError> {
+    if signature.public_key.is_empty() || !signature.is_valid {
+        return Err(CommitmentError::MissingPublicKey);
+    }
+    Ok(&signature.public_key)
+}
+
+/// Generate a salted commitment hash from the GST certificate data
+pub fn gst_generate_commitment(
+    gst: &GSTCertificate,
+    salt: [u8; 32],
+) -> Result<[u8; 32], CommitmentError> {
+    gst.commitment(salt)
+}
+
+/// Bytes hashed by `gst_generate_commitment`, so `keccak256(preimage)` is the commitment
+pub fn gst_commitment_preimage(
+    gst: &GSTCertificate,
+    salt: [u8; 32],
+) -> Result<Vec<u8>, CommitmentError> {
+    gst.commitment_preimage(salt)
+}
+
+/// Unsalted GST commitment, kept for fixtures generated before salting was introduced
+pub fn gst_generate_commitment_unsalted(gst: &GSTCertificate) -> [u8; 32] {
+    let mut combined_input = Vec::new();
+    combined_input.extend_from_slice(&gst.signature.message_digest);
+    combined_input.push(gst.state_code);
+    combined_input.extend_from_slice(gst.gst_number.as_bytes());
+    combined_input.extend_from_slice(gst.legal_name.as_bytes());
+    combined_input.extend_from_slice(&gst.signature.public_key);
+
+    keccak256(&combined_input).0
+}
+
+/// Commitment to the full text of a signed PDF, unlike the `*_generate_commitment`
+/// field commitments, which only cover the extracted fields.
+///
+/// Each page is whitespace-normalized like `normalize_address` and encoded as its own
+/// field after the message digest, so page boundaries are part of the commitment.
+pub fn full_document_commitment(pages: &[String], sig: &PdfSignatureResult) -> [u8; 32] {
+    let pages: Vec<String> = pages.iter().map(|page| collapse_whitespace(page)).collect();
+    let fields: Vec<&[u8]> = [DOCUMENT_COMMITMENT_DOMAIN, sig.message_digest.as_slice()]
+        .into_iter()
+        .chain(pages.iter().map(|page| page.as_bytes()))
+        .collect();
+
+    keccak256(canonical_encode(&fields)).into()
+}
+
+/// Compares two commitments in constant time.
+///
+/// Every byte is inspected regardless of where the first difference is, so timing does
+/// not leak how much of a recomputed commitment matches the expected one.
+pub fn commitments_equal(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
+/// keccak256 hashes of signer public keys from known issuers.
+///
+/// Only GSTN is pinned: no signed PAN, driving licence or e-Aadhaar sample is bundled to
+/// take NSDL's, a transport department's or UIDAI's key from. Used as the
+/// `trusted_issuers` allowlist it therefore fails closed for those kinds, which are
+/// rejected with `DocumentVerificationError::UntrustedIssuer` until their keys are added.
+pub const KNOWN_ISSUER_KEY_HASHES: &[[u8; 32]] = &[
+    // GSTN, signer of samples/GST-certificate.pdf
+    [
+        0xaf, 0x17, 0x4c, 0x33, 0xa4, 0x62, 0x8f, 0x49, 0xa1, 0x10, 0x6a, 0xd8, 0x29, 0xc3, 0x04,
+        0x15, 0x62, 0x7c, 0xf8, 0xea, 0x63, 0x36, 0xed, 0x74, 0x11, 0xf3, 0xc3, 0x27, 0xbc, 0x25,
+        0xb6, 0x4f,
+    ],
+];
+
+/// keccak256 hashes of UIDAI e-Aadhaar signer public keys.
+///
+/// Empty until UIDAI's signer key is pinned from a signed e-Aadhaar sample in `samples/`;
+/// until then every e-Aadhaar is rejected with `AadhaarVerificationError::UntrustedIssuer`,
+/// which is why e-Aadhaar verification sits behind the off-by-default `aadhaar` feature.
+pub const UIDAI_ISSUER_KEY_HASHES: &[[u8; 32]] = &[];
+
+/// Whether the PDF was signed by a pinned UIDAI key.
+///
+/// Only the key counts: anyone can issue themselves a certificate naming UIDAI.
+pub fn is_uidai_signer(sig: &PdfSignatureResult) -> bool {
+    let public_key_hash: [u8; 32] = keccak256(&sig.public_key).into();
+    is_trusted_issuer(&public_key_hash, UIDAI_ISSUER_KEY_HASHES)
+}
+
+/// Whether the signer's public key hash appears in the allowlist
+pub fn is_trusted_issuer(public_key_hash: &[u8; 32], allowlist: &[[u8; 32]]) -> bool {
+    allowlist
+        .iter()
+        .any(|trusted| commitments_equal(public_key_hash, trusted))
+}
+
+/// Selects which GST certificate fields, and the PAN holder's gender, are revealed in the
+/// public values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisclosureMask {
+    pub gst_number: bool,
+    pub legal_name: bool,
+    pub address: bool,
+    pub gender: bool,
+}
+
+impl DisclosureMask {
+    /// Reveal every field, matching the plain `GSTValuesStruct` output
+    pub const REVEAL_ALL: DisclosureMask = DisclosureMask {
+        gst_number: true,
+        legal_name: true,
+        address: true,
+        gender: true,
+    };
+
+    /// Whether every GST certificate field is revealed
+    pub fn reveals_all(&self) -> bool {
+        self.gst_number && self.legal_name && self.address
+    }
+}
+
+impl Default for DisclosureMask {
+    fn default() -> Self {
+        Self::REVEAL_ALL
+    }
+}
+
+/// Generate a salted commitment hash from the GST certificate data, binding hidden fields
+/// through their keccak256 hash.
+///
+/// With `DisclosureMask::REVEAL_ALL` this is identical to `gst_generate_commitment`.
+pub fn gst_generate_commitment_masked(
+    gst: &GSTCertificate,
+    mask: &DisclosureMask,
+    salt: [u8; 32],
+) -> Result<[u8; 32], CommitmentError> {
+    let fields = gst_commitment_fields(gst, mask)?;
+    Ok(fields_commitment(salt, GST_COMMITMENT_DOMAIN, &fields))
+}
+
+fn gst_commitment_fields(
+    gst: &GSTCertificate,
+    mask: &DisclosureMask,
+) -> Result<Vec<Vec<u8>>, CommitmentError> {
+    if gst.text_source == TextSource::Ocr {
+        return Err(CommitmentError::OcrText);
+    }
+    let public_key = signed_public_key(&gst.signature)?;
+
+    let mut fields = vec![
+        gst.signature.message_digest.clone(),
+        vec![gst.state_code],
+        reveal_or_hash(mask.gst_number, gst.gst_number.as_bytes()),
+        reveal_or_hash(mask.legal_name, gst.legal_name.as_bytes()),
+        public_key.to_vec(),
+    ];
+    // An absent trade name adds no field, so it never collides with an empty one
+    if let Some(trade_name) = &gst.trade_name {
+        fields.push(trade_name.as_bytes().to_vec());
+    }
+    if let Some(address) = &gst.address {
+        fields.extend([
+            GST_ADDRESS_FIELD_TAG.to_vec(),
+            reveal_or_hash(mask.address, address.as_bytes()),
+        ]);
+    }
+    if let Some(registration_type) = &gst.registration_type {
+        fields.extend([
+            GST_REGISTRATION_TYPE_FIELD_TAG.to_vec(),
+            registration_type.as_str().as_bytes().to_vec(),
+        ]);
+    }
+    Ok(fields)
+}
+
+impl Commitable for GSTCertificate {
+    const DOMAIN: &'static [u8] = GST_COMMITMENT_DOMAIN;
+
+    fn commitment_fields(&self) -> Result<Vec<Vec<u8>>, CommitmentError> {
+        gst_commitment_fields(self, &DisclosureMask::REVEAL_ALL)
+    }
+}
+
+/// Normalize an extracted name so the same entity always produces the same commitment.
+///
+/// Zero-width characters are removed, whitespace runs (including non-breaking spaces)
+/// collapse to a single space, and the result is uppercased.
+pub fn normalize_name(raw: &str) -> String {
+    collapse_whitespace(raw).to_uppercase()
+}
+
+/// Legal-form suffixes dropped by `name_variants`, longest first so "PRIVATE LIMITED" is
+/// stripped whole rather than leaving "PRIVATE"
+const LEGAL_NAME_SUFFIXES: &[&str] = &[
+    "LIMITED LIABILITY PARTNERSHIP",
+    "PRIVATE LIMITED",
+    "PRIVATE LTD",
+    "PVT LIMITED",
+    "PVT LTD",
+    "LIMITED",
+    "LTD",
+    "LLP",
+];
+
+/// The normalized name followed by the name without its legal-form suffix, if it has one.
+///
+/// "Acme Traders Pvt. Ltd." yields `["ACME TRADERS PVT. LTD.", "ACME TRADERS"]`. Meant for
+/// matching names off-chain; the committed legal name is always the full form.
+pub fn name_variants(name: &str) -> Vec<String> {
+    let normalized = normalize_name(name);
+    if normalized.is_empty() {
+        return Vec::new();
+    }
+
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    let bare = |word: &str| {
+        word.trim_matches(|c: char| c == '.' || c == ',')
+            .to_string()
+    };
+    let stripped = LEGAL_NAME_SUFFIXES.iter().find_map(|suffix| {
+        let suffix: Vec<&str> = suffix.split(' ').collect();
+        let keep = words
+            .len()
+            .checked_sub(suffix.len())
+            .filter(|&keep| keep > 0)?;
+        words[keep..]
+            .iter()
+            .zip(&suffix)
+            .all(|(word, suffix_word)| bare(word) == *suffix_word)
+            .then(|| words[..keep].join(" ").trim_end_matches(',').to_string())
+    });
+
+    let mut variants = vec![normalized];
+    variants.extend(stripped.filter(|stripped| !stripped.is_empty()));
+    variants
+}
+
+/// Normalize a name that may be printed in both English and a regional script.
+///
+/// When the name has any English words, only those are kept, along with the `&`, `.` and
+/// `,` within them, so "/ विधिक नाम ACME TRADERS / एक्मे ट्रेडर्स" becomes "ACME TRADERS". A
+/// name printed only in a regional script is normalized as is.
+pub fn normalize_bilingual_name(raw: &str) -> String {
+    let is_latin_word = |word: &str| word.is_ascii() && word.chars().any(|c| c != '/');
+    let latin_words: Vec<&str> = raw
+        .split_whitespace()
+        .filter(|w| is_latin_word(w))
+        .collect();
+
+    if latin_words
+        .iter()
+        .any(|word| word.chars().any(|c| c.is_ascii_alphabetic()))
+    {
+        normalize_name(&latin_words.join(" "))
+    } else {
+        normalize_name(raw)
+    }
+}
+
+/// Masks all but the last four characters of an identifier such as a GSTIN or PAN
+pub fn mask_identifier(id: &str) -> String {
+    let hidden = id.chars().count().saturating_sub(4);
+    id.chars()
+        .enumerate()
+        .map(|(i, c)| if i < hidden { '*' } else { c })
+        .collect()
+}
+
+/// Logs at `debug` whether an extracted field was found. The value itself is only logged
+/// at `trace`, since most fields are personal data.
+pub(crate) fn log_field<T: fmt::Debug>(document: &str, field: &str, value: Option<T>) {
+    match value {
+        Some(value) => {
+            log::debug!("{}: {} matched", document, field);
+            log::trace!("{}: {} = {:?}", document, field, value);
+        }
+        None => log::debug!("{}: {} not found", document, field),
+    }
+}
+
+/// Normalize an extracted address the way `normalize_name` does, keeping its case and
+/// dropping trailing separators left over from the line it was cut at.
+pub fn normalize_address(raw: &str) -> String {
+    collapse_whitespace(raw)
+        .trim_end_matches([',', ';', ' '])
+        .to_string()
+}
+
+/// An additional place of business listed in Annexure A of a REG-06 certificate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressEntry {
+    /// Serial number printed in the annexure table
+    pub serial: u32,
+    pub address: String,
+}
+
+/// The annexure pages of a REG-06 certificate
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GstAnnexure {
+    /// Annexure A: places of business besides the principal one, in printed order
+    pub additional_places: Vec<AddressEntry>,
+    /// Annexure B: proprietor, partners, directors, karta or committee members, each
+    /// normalized like `normalize_name`
+    pub partners: Vec<String>,
+}
+
+/// Reads Annexure A and B of a REG-06 certificate, or `None` if no page has either.
+///
+/// Only the text after an "Annexure A"/"Annexure B" heading is scanned, up to the next
+/// heading or the end of its page, so fields on the certificate page itself are never
+/// read as annexure entries.
+pub fn extract_annexure(pages: &[String]) -> Option<GstAnnexure> {
+    let mut annexure: Option<GstAnnexure> = None;
+    for page in pages {
+        let headings: Vec<_> = GST_ANNEXURE_REGEX.captures_iter(page).collect();
+        for (i, heading) in headings.iter().enumerate() {
+            let start = heading.get(0).map_or(0, |m| m.end());
+            let end = headings
+                .get(i + 1)
+                .and_then(|next| next.get(0))
+                .map_or(page.len(), |m| m.start());
+            let section = &page[start..end];
+
+            let annexure = annexure.get_or_insert_with(GstAnnexure::default);
+            if heading[1].eq_ignore_ascii_case("A") {
+                annexure
+                    .additional_places
+                    .extend(annexure_addresses(section));
+            } else {
+                annexure.partners.extend(
+                    GST_ANNEXURE_NAME_REGEX
+                        .captures_iter(section)
+                        .map(|cap| normalize_name(&cap[1]))
+                        .filter(|name| !name.is_empty()),
+                );
             }
-            PANVerificationError::LegalNameNotFound => {
-                write!(f, "Legal name not found in PDF")
+        }
+    }
+    annexure
+}
+
+/// Rows of an Annexure A table: each starts at a serial number and runs over the following
+/// lines until the next serial number or the end of the table
+fn annexure_addresses(section: &str) -> Vec<AddressEntry> {
+    let mut rows: Vec<(u32, Vec<&str>)> = Vec::new();
+    for line in section.lines() {
+        if GST_ANNEXURE_END_REGEX.is_match(line) {
+            break;
+        }
+        match GST_ANNEXURE_SERIAL_REGEX.captures(line) {
+            Some(cap) => {
+                let serial = cap[1].parse().unwrap_or_default();
+                rows.push((serial, cap.get(2).map(|m| m.as_str()).into_iter().collect()));
             }
-            PANVerificationError::DOBNotFound => {
-                write!(f, "DOB not found in PDF")
+            // Header lines come before the first row and are skipped
+            None => {
+                if let Some((_, lines)) = rows.last_mut() {
+                    lines.push(line);
+                }
             }
         }
     }
+
+    rows.into_iter()
+        .map(|(serial, lines)| AddressEntry {
+            serial,
+            address: normalize_address(&lines.join(" ")),
+        })
+        .filter(|entry| !entry.address.is_empty())
+        .collect()
+}
+
+/// Removes zero-width characters and collapses whitespace runs into single spaces
+fn collapse_whitespace(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+            )
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Extract every GSTIN in the text, deduplicated in order of first appearance.
+///
+/// GSTIN-shaped strings whose first two digits are not a known state code are skipped.
+pub fn extract_all_gstins(text: &str) -> Vec<String> {
+    let mut gstins: Vec<String> = Vec::new();
+    for cap in GST_NUMBER_REGEX.captures_iter(text) {
+        let gstin = &cap[1];
+        let known_state = gstin[..2].parse().ok().and_then(gstin_state).is_some();
+        if known_state && !gstins.iter().any(|seen| seen == gstin) {
+            gstins.push(gstin.to_string());
+        }
+    }
+    gstins
+}
+
+/// Every GSTIN of a special taxpayer type in the text with its category, deduplicated in
+/// order of first appearance; unknown state codes are skipped like in `extract_all_gstins`
+pub fn extract_special_gstins(text: &str) -> Vec<(String, GstinCategory)> {
+    let mut gstins: Vec<(String, GstinCategory)> = Vec::new();
+    for cap in GST_SPECIAL_NUMBER_REGEX.captures_iter(text) {
+        let gstin = &cap[1];
+        let known_state = gstin[..2].parse().ok().and_then(gstin_state).is_some();
+        if !known_state || gstins.iter().any(|(seen, _)| seen == gstin) {
+            continue;
+        }
+        if let Some(category) = gstin_category(gstin) {
+            gstins.push((gstin.to_string(), category));
+        }
+    }
+    gstins
 }
 
-impl Error for PANVerificationError {}
+/// Taxpayer type of a GSTIN, told apart by its structure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GstinCategory {
+    /// A regular registration: state code, PAN, entity number, `Z`, check character
+    Standard,
+    /// Unique Identification Number of a UN body, embassy or other notified person
+    Uin,
+    /// A deductor of tax at source, registered under its TAN
+    Tds,
+    /// A non-resident supplier of online information and database access services
+    Oidar,
+}
+
+impl GstinCategory {
+    /// Every category except `Standard`
+    pub fn is_special(self) -> bool {
+        self != GstinCategory::Standard
+    }
+}
+
+impl fmt::Display for GstinCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            GstinCategory::Standard => "standard",
+            GstinCategory::Uin => "UIN",
+            GstinCategory::Tds => "TDS",
+            GstinCategory::Oidar => "OIDAR",
+        })
+    }
+}
+
+/// Category of a GSTIN-shaped string, or `None` if it fits none of them.
+///
+/// Only the structure is checked; use `validate_gstin_checksum` for the check character.
+pub fn gstin_category(gstin: &str) -> Option<GstinCategory> {
+    let caps = GSTIN_CATEGORY_REGEX.captures(gstin)?;
+    [
+        ("standard", GstinCategory::Standard),
+        ("uin", GstinCategory::Uin),
+        ("tds", GstinCategory::Tds),
+        ("oidar", GstinCategory::Oidar),
+    ]
+    .into_iter()
+    .find(|(group, _)| caps.name(group).is_some())
+    .map(|(_, category)| category)
+}
+
+/// Positions of a GSTIN that can only hold digits: the state code and the PAN's digits
+const GSTIN_DIGIT_POSITIONS: [usize; 6] = [0, 1, 7, 8, 9, 10];
+
+/// Reads `O` as `0` and `I` as `1` in the digit positions of a GSTIN-shaped string.
+///
+/// Returns the corrected GSTIN only if its checksum is valid, so a misread that happens
+/// to fit the shape is not turned into a different registration.
+pub fn correct_gstin_confusables(candidate: &str) -> Option<String> {
+    if candidate.len() != 15 || !candidate.is_ascii() {
+        return None;
+    }
+    let corrected: String = candidate
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            'O' if GSTIN_DIGIT_POSITIONS.contains(&i) => '0',
+            'I' if GSTIN_DIGIT_POSITIONS.contains(&i) => '1',
+            c => c,
+        })
+        .collect();
+
+    validate_gstin_checksum(&corrected).then_some(corrected)
+}
+
+/// Every GSTIN in the text that only matches after `correct_gstin_confusables`, as
+/// (text as printed, corrected GSTIN) pairs in order of appearance
+pub fn extract_confusable_gstins(text: &str) -> Vec<(String, String)> {
+    let mut corrections: Vec<(String, String)> = Vec::new();
+    for cap in GST_NUMBER_CONFUSABLE_REGEX.captures_iter(text) {
+        let printed = &cap[1];
+        if GST_NUMBER_REGEX.is_match(printed) || corrections.iter().any(|(seen, _)| seen == printed)
+        {
+            continue;
+        }
+        if let Some(corrected) = correct_gstin_confusables(printed) {
+            corrections.push((printed.to_string(), corrected));
+        }
+    }
+    corrections
+}
+
+/// The PAN embedded in a GSTIN (characters 3 to 12), or `""` if the GSTIN is too short
+pub fn gstin_embedded_pan(gstin: &str) -> &str {
+    gstin.get(2..12).unwrap_or_default()
+}
+
+/// Whether the GSTIN was issued to the holder of `pan`
+pub fn gstin_matches_pan(gstin: &str, pan: &str) -> bool {
+    gstin.len() == 15 && pan.len() == 10 && gstin_embedded_pan(gstin) == pan
+}
+
+/// Validate the 15th character of a GSTIN against the checksum of the first 14.
+///
+/// Every `GstinCategory` uses the same check character, so this accepts all of them.
+pub fn validate_gstin_checksum(gstin: &str) -> bool {
+    // Non-ASCII input could put a character boundary inside byte 14, so never slice it
+    gstin.len() == 15
+        && gstin.is_ascii()
+        && gstin_check_character(&gstin[..14]).map(|check| check as u8)
+            == gstin.as_bytes().get(14).copied()
+}
+
+/// Check character for the first 14 characters of a GSTIN.
+///
+/// Each character is mapped to its base-36 value, every second value is doubled,
+/// and the quotient and remainder of each product by 36 are summed.
+pub fn gstin_check_character(first14: &str) -> Option<char> {
+    const CHARSET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let bytes = first14.as_bytes();
+    if bytes.len() != 14 {
+        return None;
+    }
+
+    let mut sum = 0u32;
+    for (i, c) in bytes.iter().enumerate() {
+        let value = CHARSET.iter().position(|x| x == c)? as u32;
+        let product = value * if i % 2 == 0 { 1 } else { 2 };
+        sum += product / 36 + product % 36;
+    }
+
+    let check = (36 - sum % 36) % 36;
+    Some(CHARSET[check as usize] as char)
+}
+
+/// Look up the state or union territory for the first two digits of a GSTIN
+pub fn gstin_state(code: u8) -> Option<&'static str> {
+    let name = match code {
+        1 => "Jammu and Kashmir",
+        2 => "Himachal Pradesh",
+        3 => "Punjab",
+        4 => "Chandigarh",
+        5 => "Uttarakhand",
+        6 => "Haryana",
+        7 => "Delhi",
+        8 => "Rajasthan",
+        9 => "Uttar Pradesh",
+        10 => "Bihar",
+        11 => "Sikkim",
+        12 => "Arunachal Pradesh",
+        13 => "Nagaland",
+        14 => "Manipur",
+        15 => "Mizoram",
+        16 => "Tripura",
+        17 => "Meghalaya",
+        18 => "Assam",
+        19 => "West Bengal",
+        20 => "Jharkhand",
+        21 => "Odisha",
+        22 => "Chhattisgarh",
+        23 => "Madhya Pradesh",
+        24 => "Gujarat",
+        25 => "Daman and Diu",
+        26 => "Dadra and Nagar Haveli and Daman and Diu",
+        27 => "Maharashtra",
+        28 => "Andhra Pradesh (Old)",
+        29 => "Karnataka",
+        30 => "Goa",
+        31 => "Lakshadweep",
+        32 => "Kerala",
+        33 => "Tamil Nadu",
+        34 => "Puducherry",
+        35 => "Andaman and Nicobar Islands",
+        36 => "Telangana",
+        37 => "Andhra Pradesh",
+        38 => "Ladakh",
+        97 => "Other Territory",
+        99 => "Centre Jurisdiction",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// GST
+#[derive(Debug, thiserror::Error)]
+pub enum GSTVerificationError {
+    #[error("Failed to read PDF: {0}")]
+    ReadFailed(String),
+    /// See `SignedPdfError::Encrypted`; the unencrypted original is needed
+    #[error("PDF is encrypted")]
+    EncryptedPdf,
+    /// The PDF or its text could not be parsed; retrying, e.g. with a fresh download,
+    /// may succeed
+    #[error("Failed to parse PDF: {0}")]
+    PdfParseFailed(String),
+    /// The signature does not verify against the signed bytes; must be rejected
+    #[error("PDF signature is invalid")]
+    SignatureInvalid,
+    /// zkpdf failed for a reason not recognised as a parse or signature failure; not
+    /// retryable, and must be rejected
+    #[error("PDF verification failed: {0}")]
+    VerificationFailed(String),
+    /// Text outside the signed byte range; must be rejected
+    #[error("PDF content is not covered by its signature: {0}")]
+    Tampered(#[from] TamperError),
+    #[error("PDF is {size} bytes, above the {limit} byte limit")]
+    PdfTooLarge { size: usize, limit: usize },
+    #[error("PDF has no extractable text; it may be a scanned image needing OCR")]
+    NoExtractableText,
+    #[error("GST number not found in PDF")]
+    GSTNumberNotFound,
+    #[error("GST number checksum is invalid")]
+    InvalidChecksum,
+    #[error("Legal name not found in PDF")]
+    LegalNameNotFound,
+    #[error("Signer public key is not in the trusted issuer allowlist")]
+    UntrustedIssuer,
+    /// The pages could not be rendered or recognized for the OCR fallback
+    #[error("OCR failed: {0}")]
+    OcrFailed(String),
+}
+
+/// Generate a salted commitment hash from the PAN certificate data
+pub fn pan_generate_commitment(
+    pan: &PANCertificate,
+    salt: [u8; 32],
+) -> Result<[u8; 32], CommitmentError> {
+    pan.commitment(salt)
+}
+
+/// Bytes hashed by `pan_generate_commitment`, so `keccak256(preimage)` is the commitment
+pub fn pan_commitment_preimage(
+    pan: &PANCertificate,
+    salt: [u8; 32],
+) -> Result<Vec<u8>, CommitmentError> {
+    pan.commitment_preimage(salt)
+}
+
+/// Generate a salted commitment hash from the PAN data, binding the gender through its
+/// keccak256 hash when the mask hides it
+pub fn pan_generate_commitment_masked(
+    pan: &PANCertificate,
+    mask: &DisclosureMask,
+    salt: [u8; 32],
+) -> Result<[u8; 32], CommitmentError> {
+    let fields = pan_commitment_fields(pan, mask)?;
+    Ok(fields_commitment(salt, PAN_COMMITMENT_DOMAIN, &fields))
+}
+
+fn pan_commitment_fields(
+    pan: &PANCertificate,
+    mask: &DisclosureMask,
+) -> Result<Vec<Vec<u8>>, CommitmentError> {
+    let public_key = signed_public_key(&pan.signature)?;
+
+    let mut fields = vec![
+        pan.signature.message_digest.clone(),
+        pan.pan_number.as_bytes().to_vec(),
+        pan.legal_name.as_bytes().to_vec(),
+        pan.dob.as_bytes().to_vec(),
+        public_key.to_vec(),
+    ];
+    // An absent gender adds no field, keeping commitments of PANs without one unchanged
+    if pan.gender.is_some() {
+        fields.push(reveal_or_hash(mask.gender, &[gender_code(pan.gender)]));
+    }
+    Ok(fields)
+}
+
+impl Commitable for PANCertificate {
+    const DOMAIN: &'static [u8] = PAN_COMMITMENT_DOMAIN;
+
+    fn commitment_fields(&self) -> Result<Vec<Vec<u8>>, CommitmentError> {
+        pan_commitment_fields(self, &DisclosureMask::REVEAL_ALL)
+    }
+}
+
+/// Unsalted PAN commitment, kept for fixtures generated before salting was introduced
+pub fn pan_generate_commitment_unsalted(pan: &PANCertificate) -> [u8; 32] {
+    let mut combined_input = Vec::new();
+    combined_input.extend_from_slice(&pan.signature.message_digest);
+    combined_input.extend_from_slice(pan.pan_number.as_bytes());
+    combined_input.extend_from_slice(pan.legal_name.as_bytes());
+    combined_input.extend_from_slice(pan.dob.as_bytes());
+    combined_input.extend_from_slice(&pan.signature.public_key);
+
+    keccak256(&combined_input).0
+}
+
+/// Cardholder and father's names from PAN text, each normalized.
+///
+/// The cardholder's label is a bare "Name"; "Father's Name" is never taken as the holder.
+pub fn pan_names(text: &str) -> (Option<String>, Option<String>) {
+    pan_names_with(text, &PAN_NAME_VALUE_REGEX)
+}
+
+/// Like `pan_names`, reading each name with `value_regex`, e.g. `PanConfig::name_value_regex`
+pub fn pan_names_with(text: &str, value_regex: &Regex) -> (Option<String>, Option<String>) {
+    let mut holder = None;
+    let mut father = None;
+
+    for label in PAN_NAME_LABEL_REGEX.captures_iter(text) {
+        let slot = if label.get(1).is_some() {
+            &mut father
+        } else {
+            &mut holder
+        };
+        if slot.is_some() {
+            continue;
+        }
+
+        let label_end = label.get(0).map_or(0, |m| m.end());
+        *slot = value_regex
+            .captures(&text[label_end..])
+            .and_then(|cap| cap.get(1))
+            .map(|m| normalize_name(m.as_str()))
+            .filter(|name| !name.is_empty());
+    }
+
+    (holder, father)
+}
+
+/// Holder type encoded in the 4th character of a PAN
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PanCategory {
+    Individual,
+    Company,
+    HinduUndividedFamily,
+    Firm,
+    AssociationOfPersons,
+    BodyOfIndividuals,
+    Trust,
+    LocalAuthority,
+    ArtificialJuridicalPerson,
+    Government,
+}
+
+/// Parse the holder category from the 4th character of a PAN
+pub fn pan_holder_category(pan: &str) -> Option<PanCategory> {
+    match pan.chars().nth(3)? {
+        'P' => Some(PanCategory::Individual),
+        'C' => Some(PanCategory::Company),
+        'H' => Some(PanCategory::HinduUndividedFamily),
+        'F' => Some(PanCategory::Firm),
+        'A' => Some(PanCategory::AssociationOfPersons),
+        'B' => Some(PanCategory::BodyOfIndividuals),
+        'T' => Some(PanCategory::Trust),
+        'L' => Some(PanCategory::LocalAuthority),
+        'J' => Some(PanCategory::ArtificialJuridicalPerson),
+        'G' => Some(PanCategory::Government),
+        _ => None,
+    }
+}
+
+/// Legal form of a GST registrant, from the certificate's "Constitution of Business"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BusinessConstitution {
+    Proprietorship,
+    Partnership,
+    LimitedLiabilityPartnership,
+    PrivateLimitedCompany,
+    PublicLimitedCompany,
+    HinduUndividedFamily,
+    Trust,
+    Society,
+    GovernmentDepartment,
+    LocalAuthority,
+    /// Any phrasing not recognised above, normalized with `normalize_name`
+    Other(String),
+}
+
+/// Map the printed constitution of business onto a `BusinessConstitution`.
+///
+/// Matching ignores case and punctuation, so "Pvt. Ltd.", "Private Limited Company" and
+/// "PRIVATE LIMITED" all map to the same variant.
+pub fn parse_business_constitution(raw: &str) -> BusinessConstitution {
+    let words = raw
+        .to_lowercase()
+        .replace(|c: char| !c.is_ascii_alphanumeric(), " ");
+    let text = format!(
+        " {} ",
+        words.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    let has = |phrases: &[&str]| phrases.iter().any(|phrase| text.contains(phrase));
+
+    if has(&[" limited liability partnership ", " llp "]) {
+        BusinessConstitution::LimitedLiabilityPartnership
+    } else if has(&[" proprietor", " sole "]) {
+        BusinessConstitution::Proprietorship
+    } else if has(&[" partnership ", " firm "]) {
+        BusinessConstitution::Partnership
+    } else if has(&[
+        " private limited ",
+        " pvt ltd ",
+        " private ltd ",
+        " pvt limited ",
+    ]) {
+        BusinessConstitution::PrivateLimitedCompany
+    } else if has(&[" public limited ", " public ltd ", " ltd ", " limited "]) {
+        BusinessConstitution::PublicLimitedCompany
+    } else if has(&[" hindu undivided family ", " huf "]) {
+        BusinessConstitution::HinduUndividedFamily
+    } else if has(&[" trust "]) {
+        BusinessConstitution::Trust
+    } else if has(&[" society ", " club ", " association of persons "]) {
+        BusinessConstitution::Society
+    } else if has(&[" government ", " govt "]) {
+        BusinessConstitution::GovernmentDepartment
+    } else if has(&[" local authority ", " municipal ", " panchayat "]) {
+        BusinessConstitution::LocalAuthority
+    } else {
+        BusinessConstitution::Other(normalize_name(raw))
+    }
+}
+
+/// GST registration scheme, from the certificate's "Type of Registration"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GstRegistrationType {
+    Regular,
+    /// Composition scheme, for small taxpayers paying tax at a flat rate on turnover
+    Composition,
+    CasualTaxable,
+    NonResident,
+    /// Any phrasing not recognised above, normalized with `normalize_name`
+    Other(String),
+}
+
+impl GstRegistrationType {
+    /// Name committed and exposed in the public values
+    pub fn as_str(&self) -> &str {
+        match self {
+            GstRegistrationType::Regular => "Regular",
+            GstRegistrationType::Composition => "Composition",
+            GstRegistrationType::CasualTaxable => "Casual Taxable Person",
+            GstRegistrationType::NonResident => "Non Resident Taxable Person",
+            GstRegistrationType::Other(raw) => raw,
+        }
+    }
+}
+
+/// Map the printed type of registration onto a `GstRegistrationType`, ignoring case and
+/// punctuation as `parse_business_constitution` does
+pub fn parse_registration_type(raw: &str) -> GstRegistrationType {
+    let words = raw
+        .to_lowercase()
+        .replace(|c: char| !c.is_ascii_alphanumeric(), " ");
+    let text = format!(
+        " {} ",
+        words.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+
+    if text.contains(" composition ") {
+        GstRegistrationType::Composition
+    } else if text.contains(" casual ") {
+        GstRegistrationType::CasualTaxable
+    } else if text.contains(" non resident ") {
+        GstRegistrationType::NonResident
+    } else if text.contains(" regular ") || text.contains(" normal taxpayer ") {
+        GstRegistrationType::Regular
+    } else {
+        GstRegistrationType::Other(normalize_name(raw))
+    }
+}
+
+/// Gender printed on an identity document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Gender {
+    Male,
+    Female,
+    Other,
+}
+
+/// Parse a printed gender; "Transgender" maps to `Gender::Other`
+pub fn parse_gender(raw: &str) -> Option<Gender> {
+    match raw.trim().to_uppercase().as_str() {
+        "MALE" | "M" => Some(Gender::Male),
+        "FEMALE" | "F" => Some(Gender::Female),
+        "TRANSGENDER" | "OTHER" | "T" => Some(Gender::Other),
+        _ => None,
+    }
+}
+
+/// Gender as committed and put in public values, with 0 meaning not printed
+pub fn gender_code(gender: Option<Gender>) -> u8 {
+    match gender {
+        None => 0,
+        Some(Gender::Male) => 1,
+        Some(Gender::Female) => 2,
+        Some(Gender::Other) => 3,
+    }
+}
+
+/// Public values `gender` when the disclosure mask hides it
+pub const GENDER_WITHHELD: u8 = u8::MAX;
+
+/// Inverse of `gender_code`; `GENDER_WITHHELD` and unknown codes give `None` as well
+pub fn gender_from_code(code: u8) -> Option<Gender> {
+    match code {
+        1 => Some(Gender::Male),
+        2 => Some(Gender::Female),
+        3 => Some(Gender::Other),
+        _ => None,
+    }
+}
+
+/// Parse a date of birth as printed on Indian identity documents.
+///
+/// Tries `DD/MM/YYYY`, `DD-MM-YYYY` and `YYYY-MM-DD`, in that order.
+pub fn parse_indian_dob(raw: &str) -> Result<NaiveDate, DateParseError> {
+    ["%d/%m/%Y", "%d-%m-%Y", "%Y-%m-%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(raw.trim(), format).ok())
+        .ok_or_else(|| DateParseError::UnrecognizedFormat(raw.to_string()))
+}
+
+/// Unix timestamp of midnight UTC on `date`, 0 when there is no date
+pub fn date_timestamp(date: Option<NaiveDate>) -> u64 {
+    date.and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date_time| date_time.and_utc().timestamp().max(0) as u64)
+        .unwrap_or(0)
+}
+
+/// Soft failure noticed while extracting a certificate, see
+/// `verify_gst_certificate_lenient` and `VerifiedDocument::warnings`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractionWarning {
+    /// No legal name matched; the certificate's `legal_name` is left empty
+    MissingLegalName,
+    /// Checksum validation was disabled, so GSTINs were accepted unchecked
+    ChecksumSkipped,
+    /// This GSTIN was ignored because its checksum is wrong
+    InvalidChecksum(String),
+    /// This many valid GSTINs were found; the first is the primary registration
+    MultipleGstinsFound(usize),
+    /// A GSTIN of a special taxpayer type was found; it is only used when `accepted`
+    SpecialGstin {
+        gstin: String,
+        category: GstinCategory,
+        accepted: bool,
+    },
+    /// A GSTIN printed with `O`/`I` for `0`/`1` was read as the corrected value
+    ConfusablesCorrected {
+        printed: String,
+        corrected: String,
+    },
+    MissingRegistrationDate,
+    /// Only the first `max_chars` characters of the text were scanned for fields
+    TextTruncated {
+        max_chars: usize,
+    },
+    /// No certificate could be extracted at all
+    ExtractionFailed(String),
+}
+
+impl fmt::Display for ExtractionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtractionWarning::MissingLegalName => write!(f, "Legal name not found"),
+            ExtractionWarning::ChecksumSkipped => write!(f, "GSTIN checksums were not validated"),
+            ExtractionWarning::InvalidChecksum(gstin) => {
+                write!(f, "Ignored GSTIN {} with an invalid checksum", gstin)
+            }
+            ExtractionWarning::MultipleGstinsFound(count) => {
+                write!(f, "Found {} GSTINs; using the first", count)
+            }
+            ExtractionWarning::SpecialGstin {
+                gstin,
+                category,
+                accepted,
+            } => write!(
+                f,
+                "Found {} GSTIN {}; {}",
+                category,
+                gstin,
+                if *accepted { "accepted" } else { "ignored" }
+            ),
+            ExtractionWarning::ConfusablesCorrected { printed, corrected } => {
+                write!(f, "Read GSTIN {} as {}", printed, corrected)
+            }
+            ExtractionWarning::MissingRegistrationDate => {
+                write!(f, "Registration date not found")
+            }
+            ExtractionWarning::TextTruncated { max_chars } => {
+                write!(f, "Only the first {} characters were scanned", max_chars)
+            }
+            ExtractionWarning::ExtractionFailed(msg) => write!(f, "Extraction failed: {}", msg),
+        }
+    }
+}
+
+/// Errors from decoding the program's public values
+#[derive(Debug)]
+pub enum PublicValuesError {
+    UnsupportedVersion(u16),
+    UnknownKind,
+    Malformed(String),
+}
+
+impl fmt::Display for PublicValuesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PublicValuesError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported public values schema version: {}", version)
+            }
+            PublicValuesError::UnknownKind => {
+                write!(f, "Public values do not start with a known doc_kind")
+            }
+            PublicValuesError::Malformed(msg) => {
+                write!(f, "Malformed public values: {}", msg)
+            }
+        }
+    }
+}
+
+impl Error for PublicValuesError {}
+
+#[derive(Debug)]
+pub enum DateParseError {
+    UnrecognizedFormat(String),
+}
+
+impl fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateParseError::UnrecognizedFormat(raw) => {
+                write!(f, "Unrecognized date format: {}", raw)
+            }
+        }
+    }
+}
+
+impl Error for DateParseError {}
+
+/// Whether someone born on `dob` is at least `threshold_years` old on `as_of`
+pub fn prove_age_threshold(dob: &NaiveDate, threshold_years: u32, as_of: NaiveDate) -> bool {
+    as_of
+        .years_since(*dob)
+        .is_some_and(|age| age >= threshold_years)
+}
+
+/// PAN
+#[derive(Debug, thiserror::Error)]
+pub enum PANVerificationError {
+    /// zkpdf failed for a reason not recognised as a parse or signature failure, see
+    /// `GSTVerificationError::VerificationFailed`
+    #[error("PDF verification failed: {0}")]
+    PdfVerificationFailed(String),
+    #[error("Failed to parse PDF: {0}")]
+    PdfParseFailed(String),
+    #[error("PDF signature is invalid")]
+    SignatureInvalid,
+    #[error("PDF content is not covered by its signature: {0}")]
+    Tampered(#[from] TamperError),
+    #[error("PDF is encrypted")]
+    EncryptedPdf,
+    #[error("PDF is {size} bytes, above the {limit} byte limit")]
+    PdfTooLarge { size: usize, limit: usize },
+    #[error("PDF has no extractable text; it may be a scanned image needing OCR")]
+    NoExtractableText,
+    #[error("PAN number not found in PDF")]
+    PANNumberNotFound,
+    #[error("Unknown PAN holder category: {0}")]
+    UnknownCategory(char),
+    #[error("Legal name not found in PDF")]
+    LegalNameNotFound,
+    #[error("DOB not found in PDF")]
+    DOBNotFound,
+}
+
+/// Generate a salted commitment hash from the Driving License data
+pub fn dl_generate_commitment(
+    dl: &DLCertificate,
+    salt: [u8; 32],
+) -> Result<[u8; 32], CommitmentError> {
+    dl.commitment(salt)
+}
+
+impl Commitable for DLCertificate {
+    const DOMAIN: &'static [u8] = DL_COMMITMENT_DOMAIN;
+
+    fn commitment_fields(&self) -> Result<Vec<Vec<u8>>, CommitmentError> {
+        let public_key = signed_public_key(&self.signature)?;
+
+        Ok(vec![
+            self.signature.message_digest.clone(),
+            self.dl_number.as_bytes().to_vec(),
+            self.holder_name.as_bytes().to_vec(),
+            public_key.to_vec(),
+        ])
+    }
+}
+
+/// Generate a salted commitment hash from the Aadhaar data. Only the last four digits of
+/// the Aadhaar number exist on the certificate, so nothing more can be committed.
+pub fn aadhaar_generate_commitment(
+    aadhaar: &AadhaarCertificate,
+    salt: [u8; 32],
+) -> Result<[u8; 32], CommitmentError> {
+    aadhaar.commitment(salt)
+}
+
+impl Commitable for AadhaarCertificate {
+    const DOMAIN: &'static [u8] = AADHAAR_COMMITMENT_DOMAIN;
+
+    fn commitment_fields(&self) -> Result<Vec<Vec<u8>>, CommitmentError> {
+        let public_key = signed_public_key(&self.signature)?;
+
+        Ok(vec![
+            self.signature.message_digest.clone(),
+            self.masked_uid_last4.as_bytes().to_vec(),
+            self.name.as_bytes().to_vec(),
+            self.dob.as_bytes().to_vec(),
+            vec![gender_code(self.gender)],
+            public_key.to_vec(),
+        ])
+    }
+}
+
+/// Driving License
+#[derive(Debug)]
+pub enum DLVerificationError {
+    /// zkpdf failed for a reason not recognised as a parse or signature failure, see
+    /// `GSTVerificationError::VerificationFailed`
+    PdfVerificationFailed(String),
+    PdfParseFailed(String),
+    SignatureInvalid,
+    Tampered(TamperError),
+    EncryptedPdf,
+    PdfTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    NoExtractableText,
+    DLNumberNotFound,
+    HolderNameNotFound,
+    IssuingAuthorityNotFound,
+    LicenseTypeNotFound,
+    ExpiryDateNotFound,
+}
+
+impl fmt::Display for DLVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DLVerificationError::PdfVerificationFailed(msg) => {
+                write!(f, "PDF verification failed: {}", msg)
+            }
+            DLVerificationError::PdfParseFailed(msg) => write!(f, "Failed to parse PDF: {}", msg),
+            DLVerificationError::SignatureInvalid => write!(f, "PDF signature is invalid"),
+            DLVerificationError::Tampered(err) => {
+                write!(f, "PDF content is not covered by its signature: {}", err)
+            }
+            DLVerificationError::EncryptedPdf => write!(f, "PDF is encrypted"),
+            DLVerificationError::PdfTooLarge { size, limit } => {
+                write!(f, "PDF is {} bytes, above the {} byte limit", size, limit)
+            }
+            DLVerificationError::NoExtractableText => {
+                write!(
+                    f,
+                    "PDF has no extractable text; it may be a scanned image needing OCR"
+                )
+            }
+            DLVerificationError::DLNumberNotFound => {
+                write!(f, "Driving license number not found in PDF")
+            }
+            DLVerificationError::HolderNameNotFound => {
+                write!(f, "Holder name not found in PDF")
+            }
+            DLVerificationError::IssuingAuthorityNotFound => {
+                write!(f, "Issuing authority not found in PDF")
+            }
+            DLVerificationError::LicenseTypeNotFound => {
+                write!(f, "License type not found in PDF")
+            }
+            DLVerificationError::ExpiryDateNotFound => {
+                write!(f, "Expiry date not found in PDF")
+            }
+        }
+    }
+}
+
+impl Error for DLVerificationError {}
+
+/// Aadhaar
+#[derive(Debug)]
+pub enum AadhaarVerificationError {
+    /// zkpdf failed for a reason not recognised as a parse or signature failure, see
+    /// `GSTVerificationError::VerificationFailed`
+    PdfVerificationFailed(String),
+    PdfParseFailed(String),
+    SignatureInvalid,
+    Tampered(TamperError),
+    EncryptedPdf,
+    PdfTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    NoExtractableText,
+    /// Built without the `aadhaar` feature
+    Disabled,
+    UntrustedIssuer,
+    MaskedNumberNotFound,
+    NameNotFound,
+    DobNotFound,
+}
+
+impl fmt::Display for AadhaarVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AadhaarVerificationError::PdfVerificationFailed(msg) => {
+                write!(f, "PDF verification failed: {}", msg)
+            }
+            AadhaarVerificationError::PdfParseFailed(msg) => {
+                write!(f, "Failed to parse PDF: {}", msg)
+            }
+            AadhaarVerificationError::SignatureInvalid => write!(f, "PDF signature is invalid"),
+            AadhaarVerificationError::Tampered(err) => {
+                write!(f, "PDF content is not covered by its signature: {}", err)
+            }
+            AadhaarVerificationError::EncryptedPdf => write!(f, "PDF is encrypted"),
+            AadhaarVerificationError::PdfTooLarge { size, limit } => {
+                write!(f, "PDF is {} bytes, above the {} byte limit", size, limit)
+            }
+            AadhaarVerificationError::NoExtractableText => {
+                write!(
+                    f,
+                    "PDF has no extractable text; it may be a scanned image needing OCR"
+                )
+            }
+            AadhaarVerificationError::Disabled => {
+                write!(f, "e-Aadhaar verification needs the `aadhaar` feature")
+            }
+            AadhaarVerificationError::UntrustedIssuer => {
+                write!(f, "PDF was not signed by UIDAI")
+            }
+            AadhaarVerificationError::MaskedNumberNotFound => {
+                write!(f, "Masked Aadhaar number not found in PDF")
+            }
+            AadhaarVerificationError::NameNotFound => {
+                write!(f, "Name not found in PDF")
+            }
+            AadhaarVerificationError::DobNotFound => {
+                write!(f, "Date of birth not found in PDF")
+            }
+        }
+    }
+}
+
+impl Error for AadhaarVerificationError {}
+
+/// Serializable view of the signature fields exposed on a certificate, with the
+/// public key and message digest hex-encoded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureSummary {
+    pub is_valid: bool,
+    pub public_key: String,
+    pub message_digest: String,
+}
+
+impl From<&PdfSignatureResult> for SignatureSummary {
+    fn from(signature: &PdfSignatureResult) -> Self {
+        Self {
+            is_valid: signature.is_valid,
+            public_key: hex::encode(&signature.public_key),
+            message_digest: hex::encode(&signature.message_digest),
+        }
+    }
+}
+
+impl TryFrom<SignatureSummary> for PdfSignatureResult {
+    type Error = hex::FromHexError;
+
+    fn try_from(summary: SignatureSummary) -> Result<Self, Self::Error> {
+        Ok(PdfSignatureResult {
+            is_valid: summary.is_valid,
+            public_key: hex::decode(summary.public_key)?,
+            message_digest: hex::decode(summary.message_digest)?,
+        })
+    }
+}
+
+/// `#[serde(with = ...)]` adapter serializing `PdfSignatureResult` as a `SignatureSummary`
+pub(crate) mod signature_serde {
+    use super::SignatureSummary;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use zkpdf_lib::PdfSignatureResult;
+
+    pub fn serialize<S: Serializer>(
+        signature: &PdfSignatureResult,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        SignatureSummary::from(signature).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<PdfSignatureResult, D::Error> {
+        SignatureSummary::deserialize(deserializer)?
+            .try_into()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Where a certificate's extracted fields were read from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextSource {
+    /// The PDF's text layer, which the signature covers
+    #[default]
+    Signed,
+    /// OCR of the rendered pages. The signature still covers the PDF, but nothing binds
+    /// the recognized text to it, so the fields carry only the OCR engine's assurance.
+    Ocr,
+}
+
+/// Hash algorithm of the signed message digest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DigestAlgo {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    #[default]
+    Unknown,
+}
+
+impl DigestAlgo {
+    /// MD5 and SHA-1 have practical collision attacks, so a signature over either can
+    /// vouch for a forged document
+    pub fn is_weak(self) -> bool {
+        matches!(self, DigestAlgo::Md5 | DigestAlgo::Sha1)
+    }
+}
+
+impl fmt::Display for DigestAlgo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            DigestAlgo::Md5 => "MD5",
+            DigestAlgo::Sha1 => "SHA-1",
+            DigestAlgo::Sha224 => "SHA-224",
+            DigestAlgo::Sha256 => "SHA-256",
+            DigestAlgo::Sha384 => "SHA-384",
+            DigestAlgo::Sha512 => "SHA-512",
+            DigestAlgo::Unknown => "unknown",
+        })
+    }
+}
+
+/// The digest algorithm of the signature, inferred from the digest length.
+///
+/// zkpdf reports the digest bytes but not the CMS `digestAlgorithm`, so a digest of the
+/// same length from another family (e.g. SHA3-256) is reported as its SHA-2 namesake.
+pub fn digest_algorithm(sig: &PdfSignatureResult) -> DigestAlgo {
+    match sig.message_digest.len() {
+        16 => DigestAlgo::Md5,
+        20 => DigestAlgo::Sha1,
+        28 => DigestAlgo::Sha224,
+        32 => DigestAlgo::Sha256,
+        48 => DigestAlgo::Sha384,
+        64 => DigestAlgo::Sha512,
+        _ => DigestAlgo::Unknown,
+    }
+}
+
+/// Public key algorithm of the PDF signer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyAlgorithm {
+    Rsa,
+    Ecdsa,
+    Ed25519,
+}
+
+/// Algorithm and size of the key that signed the PDF
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyInfo {
+    pub algorithm: KeyAlgorithm,
+    pub key_bits: u32,
+}
+
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+const OID_P256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+const OID_SECP256K1: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+const OID_P384: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x22];
+const OID_P521: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x23];
+
+/// Determine the signer's key algorithm and size from the signature's public key.
+///
+/// Accepts a DER `SubjectPublicKeyInfo`, a PKCS#1 `RSAPublicKey`, or a raw
+/// uncompressed EC point.
+pub fn signature_key_info(sig: &PdfSignatureResult) -> Result<KeyInfo, SignatureParseError> {
+    let key = sig.public_key.as_slice();
+    if key.is_empty() {
+        return Err(SignatureParseError::EmptyPublicKey);
+    }
+
+    // Raw uncompressed EC point: 0x04 || X || Y
+    if key[0] == 0x04 {
+        let key_bits = match key.len() {
+            65 => 256,
+            97 => 384,
+            133 => 521,
+            _ => return Err(SignatureParseError::MalformedKey),
+        };
+        return Ok(KeyInfo {
+            algorithm: KeyAlgorithm::Ecdsa,
+            key_bits,
+        });
+    }
+
+    let (tag, body, _) = read_der(key).ok_or(SignatureParseError::MalformedKey)?;
+    if tag != 0x30 {
+        return Err(SignatureParseError::MalformedKey);
+    }
+
+    let (first_tag, first, rest) = read_der(body).ok_or(SignatureParseError::MalformedKey)?;
+    match first_tag {
+        // PKCS#1 RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+        0x02 => Ok(KeyInfo {
+            algorithm: KeyAlgorithm::Rsa,
+            key_bits: integer_bits(first),
+        }),
+        // SubjectPublicKeyInfo ::= SEQUENCE { algorithm AlgorithmIdentifier, subjectPublicKey BIT STRING }
+        0x30 => {
+            let (_, oid, params) = read_der(first).ok_or(SignatureParseError::MalformedKey)?;
+            let (bit_string_tag, bit_string, _) =
+                read_der(rest).ok_or(SignatureParseError::MalformedKey)?;
+            if bit_string_tag != 0x03 || bit_string.is_empty() {
+                return Err(SignatureParseError::MalformedKey);
+            }
+
+            if oid == OID_RSA_ENCRYPTION {
+                // Skip the unused-bits byte and read the nested RSAPublicKey
+                let (_, rsa_key, _) =
+                    read_der(&bit_string[1..]).ok_or(SignatureParseError::MalformedKey)?;
+                let (_, modulus, _) = read_der(rsa_key).ok_or(SignatureParseError::MalformedKey)?;
+                Ok(KeyInfo {
+                    algorithm: KeyAlgorithm::Rsa,
+                    key_bits: integer_bits(modulus),
+                })
+            } else if oid == OID_EC_PUBLIC_KEY {
+                let (_, curve, _) = read_der(params).ok_or(SignatureParseError::MalformedKey)?;
+                let key_bits = match curve {
+                    c if c == OID_P256 || c == OID_SECP256K1 => 256,
+                    c if c == OID_P384 => 384,
+                    c if c == OID_P521 => 521,
+                    _ => return Err(SignatureParseError::UnsupportedAlgorithm(oid_hex(curve))),
+                };
+                Ok(KeyInfo {
+                    algorithm: KeyAlgorithm::Ecdsa,
+                    key_bits,
+                })
+            } else if oid == OID_ED25519 {
+                Ok(KeyInfo {
+                    algorithm: KeyAlgorithm::Ed25519,
+                    key_bits: 256,
+                })
+            } else {
+                Err(SignatureParseError::UnsupportedAlgorithm(oid_hex(oid)))
+            }
+        }
+        _ => Err(SignatureParseError::MalformedKey),
+    }
+}
+
+/// DER X.509 certificates carried by the CMS `SignedData` of every signature in the PDF.
+///
+/// zkpdf verifies a signature with the signer's bare public key and returns only that
+/// key, so the signer certificate has to be read from the signature value itself: the
+/// hex string in the gap of each `/ByteRange`. Read these before handing the bytes to
+/// `verify_and_extract`, then pick the signer's with `signer_certificate`.
+pub fn embedded_certificates(pdf_bytes: &[u8]) -> Vec<Vec<u8>> {
+    let Ok(ranges) = signature_byte_ranges(pdf_bytes) else {
+        return Vec::new();
+    };
+
+    ranges
+        .into_iter()
+        .filter_map(|[_, gap_start, gap_end, _]| {
+            let value = pdf_bytes.get(gap_start..gap_end)?;
+            let digits: Vec<u8> = value
+                .strip_prefix(b"<")?
+                .strip_suffix(b">")?
+                .iter()
+                .copied()
+                .filter(|b| !b.is_ascii_whitespace())
+                .collect();
+            hex::decode(digits).ok()
+        })
+        .flat_map(|cms| {
+            cms_certificates(&cms)
+                .into_iter()
+                .map(<[u8]>::to_vec)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The certificate among `certificates` whose subject public key is the key zkpdf
+/// verified the signature with.
+///
+/// The CMS may also carry CA certificates, and nothing but this match ties a certificate
+/// to the signature, so a certificate is never taken on its position alone.
+pub fn signer_certificate<'a>(
+    certificates: &'a [Vec<u8>],
+    sig: &PdfSignatureResult,
+) -> Option<&'a [u8]> {
+    certificates
+        .iter()
+        .map(Vec::as_slice)
+        .find(|certificate| certificate_holds_key(certificate, &sig.public_key))
+}
+
+/// Certificates of a DER CMS `ContentInfo` holding `SignedData`
+fn cms_certificates(cms: &[u8]) -> Vec<&[u8]> {
+    // ContentInfo ::= SEQUENCE { contentType OBJECT IDENTIFIER, content [0] EXPLICIT ANY }
+    // SignedData ::= SEQUENCE { version, digestAlgorithms, encapContentInfo,
+    //                           certificates [0] IMPLICIT SET OF Certificate OPTIONAL, ... }
+    let certificate_set = || -> Option<&[u8]> {
+        let (_, content_info, _) = read_der(cms)?;
+        let (_, _, rest) = read_der(content_info)?;
+        let (tag, content, _) = read_der(rest)?;
+        if tag != 0xa0 {
+            return None;
+        }
+        let (_, mut rest, _) = read_der(content)?;
+        for _ in 0..3 {
+            rest = read_der(rest)?.2;
+        }
+        let (tag, certificates, _) = read_der(rest)?;
+        (tag == 0xa0).then_some(certificates)
+    };
+
+    let mut certificates = Vec::new();
+    let mut rest = certificate_set().unwrap_or_default();
+    while let Some((_, _, next)) = read_der(rest) {
+        certificates.push(&rest[..rest.len() - next.len()]);
+        rest = next;
+    }
+    certificates
+}
+
+/// Whether `key` is the subject public key of a DER X.509 certificate, either as the
+/// whole `SubjectPublicKeyInfo` or as its key bits
+fn certificate_holds_key(der: &[u8], key: &[u8]) -> bool {
+    !key.is_empty()
+        && certificate_public_key(der).is_some_and(|(spki, bits)| key == spki || key == bits)
+}
+
+/// The DER `SubjectPublicKeyInfo` of an X.509 certificate and its key bits, e.g. the PKCS#1
+/// `RSAPublicKey` zkpdf reports as `public_key` for RSA signers
+fn certificate_public_key(der: &[u8]) -> Option<(&[u8], &[u8])> {
+    // Skip signature, issuer, validity and subject
+    let mut rest = tbs_fields_after_serial(der)?;
+    for _ in 0..4 {
+        rest = read_der(rest)?.2;
+    }
+    let (tag, spki, next) = read_der(rest)?;
+    if tag != 0x30 {
+        return None;
+    }
+    let (_, _, bit_string) = read_der(spki)?;
+    let (tag, bits, _) = read_der(bit_string)?;
+    if tag != 0x03 {
+        return None;
+    }
+    // Skip the unused-bits byte
+    Some((&rest[..rest.len() - next.len()], bits.get(1..)?))
+}
+
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+
+/// Subject common name of the signer certificate from `signer_certificate`, e.g.
+/// "NSDL e-Governance"
+pub fn signer_common_name(certificate: &[u8]) -> Option<String> {
+    certificate_common_name(certificate)
+}
+
+/// Validity window (notBefore, notAfter) of the signer certificate from
+/// `signer_certificate`
+pub fn signer_cert_validity(certificate: &[u8]) -> Option<(NaiveDate, NaiveDate)> {
+    // Skip signature and issuer
+    let mut rest = tbs_fields_after_serial(certificate)?;
+    for _ in 0..2 {
+        rest = read_der(rest)?.2;
+    }
+
+    // Validity ::= SEQUENCE { notBefore Time, notAfter Time }
+    let (tag, validity, _) = read_der(rest)?;
+    if tag != 0x30 {
+        return None;
+    }
+    let (not_before_tag, not_before, rest) = read_der(validity)?;
+    let (not_after_tag, not_after, _) = read_der(rest)?;
+
+    Some((
+        der_time_date(not_before_tag, not_before)?,
+        der_time_date(not_after_tag, not_after)?,
+    ))
+}
+
+/// Whether the signer certificate from `signer_certificate` is self-signed.
+///
+/// With `embedded-issuers`, an RSA certificate over SHA-2 is self-signed when its
+/// signature verifies under its own key, whatever issuer it names. Otherwise only whether
+/// it names itself as its issuer is checked, which a self-signed certificate naming a
+/// real CA evades.
+pub fn signer_is_self_signed(certificate: &[u8]) -> bool {
+    #[cfg(feature = "embedded-issuers")]
+    if let Some(self_signed) = crate::issuers::signed_by_own_key(certificate) {
+        return self_signed;
+    }
+    certificate_issuer_and_subject(certificate).is_some_and(|(issuer, subject)| issuer == subject)
+}
+
+/// DER-encoded issuer and subject names of a DER X.509 certificate
+fn certificate_issuer_and_subject(der: &[u8]) -> Option<(&[u8], &[u8])> {
+    // Skip signature, then read issuer, skip validity and read subject
+    let rest = read_der(tbs_fields_after_serial(der)?)?.2;
+    let (issuer_tag, issuer, rest) = read_der(rest)?;
+    let (_, _, rest) = read_der(rest)?;
+    let (subject_tag, subject, _) = read_der(rest)?;
+    (issuer_tag == 0x30 && subject_tag == 0x30).then_some((issuer, subject))
+}
+
+/// Date of a DER UTCTime (`YYMMDDHHMMSSZ`) or GeneralizedTime (`YYYYMMDDHHMMSSZ`)
+fn der_time_date(tag: u8, value: &[u8]) -> Option<NaiveDate> {
+    let digits = |range: std::ops::Range<usize>| -> Option<u32> {
+        std::str::from_utf8(value.get(range)?).ok()?.parse().ok()
+    };
+    let (year, date_start) = match tag {
+        // RFC 5280: UTCTime years 50-99 are 19xx, 00-49 are 20xx
+        0x17 => {
+            let year = digits(0..2)?;
+            (if year >= 50 { 1900 + year } else { 2000 + year }, 2)
+        }
+        0x18 => (digits(0..4)?, 4),
+        _ => return None,
+    };
+
+    NaiveDate::from_ymd_opt(
+        i32::try_from(year).ok()?,
+        digits(date_start..date_start + 2)?,
+        digits(date_start + 2..date_start + 4)?,
+    )
+}
+
+/// The TBSCertificate fields of a DER X.509 certificate that follow the serial number
+pub(crate) fn tbs_fields_after_serial(der: &[u8]) -> Option<&[u8]> {
+    // Certificate ::= SEQUENCE { tbsCertificate TBSCertificate, signatureAlgorithm, signatureValue }
+    let (tag, certificate, _) = read_der(der)?;
+    if tag != 0x30 {
+        return None;
+    }
+    let (tag, tbs, _) = read_der(certificate)?;
+    if tag != 0x30 {
+        return None;
+    }
+
+    // TBSCertificate ::= SEQUENCE { [0] version OPTIONAL, serialNumber, signature, issuer,
+    //                               validity, subject, ... }
+    let (mut tag, _, mut rest) = read_der(tbs)?;
+    if tag == 0xa0 {
+        (tag, _, rest) = read_der(rest)?;
+    }
+    if tag != 0x02 {
+        return None;
+    }
+    Some(rest)
+}
+
+/// Subject CN of a DER X.509 certificate
+fn certificate_common_name(der: &[u8]) -> Option<String> {
+    // Skip signature, issuer and validity
+    let mut rest = tbs_fields_after_serial(der)?;
+    for _ in 0..3 {
+        rest = read_der(rest)?.2;
+    }
+    let (tag, subject, _) = read_der(rest)?;
+    if tag != 0x30 {
+        return None;
+    }
+
+    // Name ::= SEQUENCE OF SET OF SEQUENCE { type OBJECT IDENTIFIER, value ANY }
+    let mut rdns = subject;
+    while !rdns.is_empty() {
+        let (_, mut attributes, rest) = read_der(rdns)?;
+        rdns = rest;
+        while !attributes.is_empty() {
+            let (_, attribute, rest) = read_der(attributes)?;
+            attributes = rest;
+            let (_, oid, value) = read_der(attribute)?;
+            if oid == OID_COMMON_NAME {
+                let (tag, value, _) = read_der(value)?;
+                return match tag {
+                    // BMPString is big-endian UTF-16
+                    0x1e => String::from_utf16(
+                        &(0..value.len() / 2)
+                            .map(|i| u16::from_be_bytes([value[2 * i], value[2 * i + 1]]))
+                            .collect::<Vec<_>>(),
+                    )
+                    .ok(),
+                    _ => String::from_utf8(value.to_vec()).ok(),
+                };
+            }
+        }
+    }
+    None
+}
+
+/// Read one DER TLV, returning its tag, contents and the remaining bytes
+pub(crate) fn read_der(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first_len, rest) = rest.split_first()?;
+
+    let (len, rest) = if first_len & 0x80 == 0 {
+        (first_len as usize, rest)
+    } else {
+        let num_bytes = (first_len & 0x7f) as usize;
+        if num_bytes == 0 || num_bytes > 4 || rest.len() < num_bytes {
+            return None;
+        }
+        let len = rest[..num_bytes]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, &rest[num_bytes..])
+    };
+
+    if rest.len() < len {
+        return None;
+    }
+    Some((tag, &rest[..len], &rest[len..]))
+}
+
+/// Bit length of an unsigned big-endian DER INTEGER
+fn integer_bits(integer: &[u8]) -> u32 {
+    let significant: &[u8] = match integer.iter().position(|b| *b != 0) {
+        Some(start) => &integer[start..],
+        None => return 0,
+    };
+    (significant.len() as u32 - 1) * 8 + (8 - significant[0].leading_zeros())
+}
+
+fn oid_hex(oid: &[u8]) -> String {
+    oid.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[derive(Debug)]
+pub enum SignatureParseError {
+    EmptyPublicKey,
+    MalformedKey,
+    UnsupportedAlgorithm(String),
+}
+
+impl fmt::Display for SignatureParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignatureParseError::EmptyPublicKey => {
+                write!(f, "Signature public key is empty")
+            }
+            SignatureParseError::MalformedKey => {
+                write!(f, "Signature public key is not valid DER")
+            }
+            SignatureParseError::UnsupportedAlgorithm(oid) => {
+                write!(f, "Unsupported public key algorithm (OID {})", oid)
+            }
+        }
+    }
+}
+
+impl Error for SignatureParseError {}
+
+/// Unified error for callers that accept either a GST or a PAN certificate
+#[derive(Debug)]
+pub enum DocumentVerificationError {
+    /// zkpdf failed for a reason not recognised as a parse or signature failure, see
+    /// `GSTVerificationError::VerificationFailed`
+    PdfVerificationFailed(String),
+    PdfParseFailed(String),
+    SignatureInvalid,
+    Tampered(TamperError),
+    EncryptedPdf,
+    PdfTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    NoExtractableText,
+    Gst(GSTVerificationError),
+    Pan(PANVerificationError),
+    Dl(DLVerificationError),
+    Aadhaar(AadhaarVerificationError),
+    NoDocumentMatched,
+    /// The text matches several document types; choose one with `document_type`
+    AmbiguousDocumentType(Vec<DocumentType>),
+    PanMismatch {
+        gstin: String,
+        pan: String,
+    },
+    SignerCertificateExpired {
+        not_before: NaiveDate,
+        not_after: NaiveDate,
+        as_of: NaiveDate,
+    },
+    SignerValidityUnknown,
+    WeakDigest(DigestAlgo),
+    SelfSignedSigner,
+    /// A check of the signer certificate was requested, but the PDF does not carry it
+    SignerCertificateUnavailable,
+    /// The signer key is not in `CertificateVerifierBuilder::trusted_issuers`
+    UntrustedIssuer,
+}
+
+impl fmt::Display for DocumentVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DocumentVerificationError::PdfVerificationFailed(msg) => {
+                write!(f, "PDF verification failed: {}", msg)
+            }
+            DocumentVerificationError::PdfParseFailed(msg) => {
+                write!(f, "Failed to parse PDF: {}", msg)
+            }
+            DocumentVerificationError::SignatureInvalid => write!(f, "PDF signature is invalid"),
+            DocumentVerificationError::Tampered(err) => {
+                write!(f, "PDF content is not covered by its signature: {}", err)
+            }
+            DocumentVerificationError::EncryptedPdf => write!(f, "PDF is encrypted"),
+            DocumentVerificationError::PdfTooLarge { size, limit } => {
+                write!(f, "PDF is {} bytes, above the {} byte limit", size, limit)
+            }
+            DocumentVerificationError::NoExtractableText => {
+                write!(
+                    f,
+                    "PDF has no extractable text; it may be a scanned image needing OCR"
+                )
+            }
+            DocumentVerificationError::Gst(err) => {
+                write!(f, "GST certificate verification failed: {}", err)
+            }
+            DocumentVerificationError::Pan(err) => {
+                write!(f, "PAN certificate verification failed: {}", err)
+            }
+            DocumentVerificationError::Dl(err) => {
+                write!(f, "Driving license verification failed: {}", err)
+            }
+            DocumentVerificationError::Aadhaar(err) => {
+                write!(f, "Aadhaar verification failed: {}", err)
+            }
+            DocumentVerificationError::NoDocumentMatched => {
+                write!(f, "PDF did not match any supported document type")
+            }
+            DocumentVerificationError::AmbiguousDocumentType(types) => {
+                write!(f, "PDF matches several document types: {:?}", types)
+            }
+            DocumentVerificationError::PanMismatch { gstin, pan } => {
+                write!(f, "GSTIN {} was not issued to PAN {}", gstin, pan)
+            }
+            DocumentVerificationError::SignerCertificateExpired {
+                not_before,
+                not_after,
+                as_of,
+            } => write!(
+                f,
+                "Signer certificate is valid from {} to {}, not on {}",
+                not_before, not_after, as_of
+            ),
+            DocumentVerificationError::SignerValidityUnknown => {
+                write!(
+                    f,
+                    "Signer certificate validity cannot be read from the signature"
+                )
+            }
+            DocumentVerificationError::WeakDigest(algorithm) => {
+                write!(f, "Signature uses the weak {} digest", algorithm)
+            }
+            DocumentVerificationError::SelfSignedSigner => {
+                write!(f, "Signer certificate is self-signed")
+            }
+            DocumentVerificationError::SignerCertificateUnavailable => {
+                write!(f, "PDF does not carry the signer certificate")
+            }
+            DocumentVerificationError::UntrustedIssuer => {
+                write!(f, "PDF was not signed by a trusted issuer")
+            }
+        }
+    }
+}
+
+impl Error for DocumentVerificationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DocumentVerificationError::Gst(err) => Some(err),
+            DocumentVerificationError::Pan(err) => Some(err),
+            DocumentVerificationError::Dl(err) => Some(err),
+            DocumentVerificationError::Aadhaar(err) => Some(err),
+            DocumentVerificationError::Tampered(err) => Some(err),
+            DocumentVerificationError::PdfVerificationFailed(_)
+            | DocumentVerificationError::PdfParseFailed(_)
+            | DocumentVerificationError::SignatureInvalid
+            | DocumentVerificationError::PdfTooLarge { .. }
+            | DocumentVerificationError::EncryptedPdf
+            | DocumentVerificationError::NoExtractableText
+            | DocumentVerificationError::NoDocumentMatched
+            | DocumentVerificationError::AmbiguousDocumentType(_)
+            | DocumentVerificationError::PanMismatch { .. }
+            | DocumentVerificationError::SignerCertificateExpired { .. }
+            | DocumentVerificationError::SignerValidityUnknown
+            | DocumentVerificationError::WeakDigest(_)
+            | DocumentVerificationError::SelfSignedSigner
+            | DocumentVerificationError::SignerCertificateUnavailable
+            | DocumentVerificationError::UntrustedIssuer => None,
+        }
+    }
+}
+
+impl From<SignedPdfError> for GSTVerificationError {
+    fn from(err: SignedPdfError) -> Self {
+        match err {
+            SignedPdfError::PdfTooLarge { size, limit } => {
+                GSTVerificationError::PdfTooLarge { size, limit }
+            }
+            SignedPdfError::Encrypted => GSTVerificationError::EncryptedPdf,
+            SignedPdfError::Tampered(err) => GSTVerificationError::Tampered(err),
+            SignedPdfError::VerificationFailed(message) if is_signature_failure(&message) => {
+                GSTVerificationError::SignatureInvalid
+            }
+            SignedPdfError::VerificationFailed(message) if is_parse_failure(&message) => {
+                GSTVerificationError::PdfParseFailed(message)
+            }
+            SignedPdfError::VerificationFailed(message) => {
+                GSTVerificationError::VerificationFailed(message)
+            }
+        }
+    }
+}
+
+impl From<SignedPdfError> for PANVerificationError {
+    fn from(err: SignedPdfError) -> Self {
+        match err {
+            SignedPdfError::PdfTooLarge { size, limit } => {
+                PANVerificationError::PdfTooLarge { size, limit }
+            }
+            SignedPdfError::Encrypted => PANVerificationError::EncryptedPdf,
+            SignedPdfError::Tampered(err) => PANVerificationError::Tampered(err),
+            SignedPdfError::VerificationFailed(message) if is_signature_failure(&message) => {
+                PANVerificationError::SignatureInvalid
+            }
+            SignedPdfError::VerificationFailed(message) if is_parse_failure(&message) => {
+                PANVerificationError::PdfParseFailed(message)
+            }
+            SignedPdfError::VerificationFailed(message) => {
+                PANVerificationError::PdfVerificationFailed(message)
+            }
+        }
+    }
+}
+
+impl From<SignedPdfError> for DLVerificationError {
+    fn from(err: SignedPdfError) -> Self {
+        match err {
+            SignedPdfError::PdfTooLarge { size, limit } => {
+                DLVerificationError::PdfTooLarge { size, limit }
+            }
+            SignedPdfError::Encrypted => DLVerificationError::EncryptedPdf,
+            SignedPdfError::Tampered(err) => DLVerificationError::Tampered(err),
+            SignedPdfError::VerificationFailed(message) if is_signature_failure(&message) => {
+                DLVerificationError::SignatureInvalid
+            }
+            SignedPdfError::VerificationFailed(message) if is_parse_failure(&message) => {
+                DLVerificationError::PdfParseFailed(message)
+            }
+            SignedPdfError::VerificationFailed(message) => {
+                DLVerificationError::PdfVerificationFailed(message)
+            }
+        }
+    }
+}
+
+impl From<SignedPdfError> for AadhaarVerificationError {
+    fn from(err: SignedPdfError) -> Self {
+        match err {
+            SignedPdfError::PdfTooLarge { size, limit } => {
+                AadhaarVerificationError::PdfTooLarge { size, limit }
+            }
+            SignedPdfError::Encrypted => AadhaarVerificationError::EncryptedPdf,
+            SignedPdfError::Tampered(err) => AadhaarVerificationError::Tampered(err),
+            SignedPdfError::VerificationFailed(message) if is_signature_failure(&message) => {
+                AadhaarVerificationError::SignatureInvalid
+            }
+            SignedPdfError::VerificationFailed(message) if is_parse_failure(&message) => {
+                AadhaarVerificationError::PdfParseFailed(message)
+            }
+            SignedPdfError::VerificationFailed(message) => {
+                AadhaarVerificationError::PdfVerificationFailed(message)
+            }
+        }
+    }
+}
+
+impl From<SignedPdfError> for DocumentVerificationError {
+    fn from(err: SignedPdfError) -> Self {
+        match err {
+            SignedPdfError::PdfTooLarge { size, limit } => {
+                DocumentVerificationError::PdfTooLarge { size, limit }
+            }
+            SignedPdfError::Encrypted => DocumentVerificationError::EncryptedPdf,
+            SignedPdfError::Tampered(err) => DocumentVerificationError::Tampered(err),
+            SignedPdfError::VerificationFailed(message) if is_signature_failure(&message) => {
+                DocumentVerificationError::SignatureInvalid
+            }
+            SignedPdfError::VerificationFailed(message) if is_parse_failure(&message) => {
+                DocumentVerificationError::PdfParseFailed(message)
+            }
+            SignedPdfError::VerificationFailed(message) => {
+                DocumentVerificationError::PdfVerificationFailed(message)
+            }
+        }
+    }
+}
+
+impl From<GSTVerificationError> for DocumentVerificationError {
+    fn from(err: GSTVerificationError) -> Self {
+        DocumentVerificationError::Gst(err)
+    }
+}
+
+impl From<PANVerificationError> for DocumentVerificationError {
+    fn from(err: PANVerificationError) -> Self {
+        DocumentVerificationError::Pan(err)
+    }
+}
+
+impl From<DLVerificationError> for DocumentVerificationError {
+    fn from(err: DLVerificationError) -> Self {
+        DocumentVerificationError::Dl(err)
+    }
+}
+
+impl From<AadhaarVerificationError> for DocumentVerificationError {
+    fn from(err: AadhaarVerificationError) -> Self {
+        DocumentVerificationError::Aadhaar(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SignerInfo;
+
+    #[test]
+    fn gstin_checksum_accepts_valid_numbers() {
+        assert!(validate_gstin_checksum("27AAPFU0939F1ZV"));
+        assert!(validate_gstin_checksum("29AAGCB7383J1Z4"));
+        assert!(validate_gstin_checksum("27AAACR5055K1Z7"));
+    }
+
+    #[test]
+    fn gstin_checksum_rejects_invalid_numbers() {
+        assert!(!validate_gstin_checksum("27AAPFU0939F1ZA"));
+        assert!(!validate_gstin_checksum("29AAGCB7383J1Z5"));
+        assert!(!validate_gstin_checksum("07AAACC1206D1ZM"));
+    }
+
+    #[test]
+    fn gstin_checksum_rejects_malformed_input() {
+        assert!(!validate_gstin_checksum(""));
+        assert!(!validate_gstin_checksum("27AAPFU0939F1Z"));
+        assert!(!validate_gstin_checksum("27aapfu0939f1zv"));
+        // 15 bytes with a two-byte character straddling byte 14
+        assert!(!validate_gstin_checksum("27AAPFU0939F1é"));
+    }
+
+    fn sample_commitment(domain: &[u8]) -> [u8; 32] {
+        CommitmentInput {
+            salt: [7u8; 32],
+            domain,
+            fields: vec![b"27AAPFU0939F1ZV", b"ACME TRADERS", &[0xab; 32]],
+        }
+        .commitment()
+    }
+
+    #[test]
+    fn domain_tag_changes_commitment() {
+        let gst = sample_commitment(GST_COMMITMENT_DOMAIN);
+        let pan = sample_commitment(PAN_COMMITMENT_DOMAIN);
+        let dl = sample_commitment(DL_COMMITMENT_DOMAIN);
+
+        assert_ne!(gst, pan);
+        assert_ne!(gst, dl);
+        assert_ne!(pan, dl);
+        assert_eq!(gst, sample_commitment(GST_COMMITMENT_DOMAIN));
+    }
+
+    #[test]
+    fn commitable_builds_the_commitment_input() {
+        let mut dl = DLCertificate {
+            dl_number: "MH0220190001234".to_string(),
+            holder_name: "RAHUL KUMAR".to_string(),
+            issuing_authority: "RTO MUMBAI".to_string(),
+            license_type: "LMV".to_string(),
+            expiry_date: "01/01/2040".to_string(),
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+            signer: SignerInfo::default(),
+        };
+        let expected = CommitmentInput {
+            salt: [7u8; 32],
+            domain: DL_COMMITMENT_DOMAIN,
+            fields: vec![&[1; 32], b"MH0220190001234", b"RAHUL KUMAR", &[2; 64]],
+        }
+        .commitment();
+        assert_eq!(dl.commitment([7u8; 32]).unwrap(), expected);
+        assert_eq!(dl_generate_commitment(&dl, [7u8; 32]).unwrap(), expected);
+
+        dl.signature.is_valid = false;
+        assert!(matches!(
+            dl.commitment([7u8; 32]),
+            Err(CommitmentError::MissingPublicKey)
+        ));
+    }
+
+    /// A signed GST certificate with no optional fields; tests override the ones they
+    /// exercise
+    fn gst() -> GSTCertificate {
+        GSTCertificate {
+            gst_number: "27AAPFU0939F1ZV".to_string(),
+            additional_gst_numbers: Vec::new(),
+            legal_name: "ACME TRADERS".to_string(),
+            trade_name: None,
+            registration_date: None,
+            address: None,
+            constitution: None,
+            state_code: 27,
+            state_name: None,
+            registration_type: None,
+            email: None,
+            mobile_last4: None,
+            legal_name_variants: Vec::new(),
+            annexure: None,
+            text_source: TextSource::Signed,
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+            signer: SignerInfo::default(),
+        }
+    }
+
+    #[test]
+    fn gst_commitment_preimage_hashes_to_the_commitment() {
+        let mut cert = GSTCertificate {
+            trade_name: Some("ACME".to_string()),
+            address: Some("1 MG ROAD, MUMBAI".to_string()),
+            ..gst()
+        };
+
+        let preimage = gst_commitment_preimage(&cert, [7u8; 32]).unwrap();
+        assert_eq!(&preimage[..32], &[7u8; 32]);
+        assert_eq!(
+            keccak256(&preimage).0,
+            gst_generate_commitment(&cert, [7u8; 32]).unwrap()
+        );
+
+        cert.signature.is_valid = false;
+        assert!(matches!(
+            gst_commitment_preimage(&cert, [7u8; 32]),
+            Err(CommitmentError::MissingPublicKey)
+        ));
+    }
+
+    #[test]
+    fn ocr_certificates_are_not_committed_as_signed() {
+        let signed = gst();
+        let ocr = GSTCertificate {
+            text_source: TextSource::Ocr,
+            ..gst()
+        };
+
+        assert!(matches!(
+            gst_generate_commitment(&ocr, [7u8; 32]),
+            Err(CommitmentError::OcrText)
+        ));
+        let ctx = crate::CommitContext {
+            disclosure: DisclosureMask::REVEAL_ALL,
+            salt: [7u8; 32],
+            document_commitment: [0u8; 32],
+            committed_match: false,
+            include_public_key: false,
+            packed: false,
+            proven_at: 0,
+        };
+        assert!(matches!(
+            crate::gst_public_values(&ocr, &ctx),
+            Err(CommitmentError::OcrText)
+        ));
+        assert!(!crate::GSTValuesStruct::from((&ocr, [0u8; 32], [0u8; 32])).signature_valid);
+        assert_ne!(signed, ocr);
+    }
+
+    #[test]
+    fn commitment_preimage_is_length_prefixed() {
+        let input = CommitmentInput {
+            salt: [0u8; 32],
+            domain: GST_COMMITMENT_DOMAIN,
+            fields: vec![b"ab", b"c"],
+        };
+        let shifted = CommitmentInput {
+            salt: [0u8; 32],
+            domain: GST_COMMITMENT_DOMAIN,
+            fields: vec![b"a", b"bc"],
+        };
+
+        let bytes = input.to_bytes();
+        assert_eq!(
+            &bytes[32..36],
+            &(GST_COMMITMENT_DOMAIN.len() as u32).to_be_bytes()
+        );
+        assert_ne!(input.commitment(), shifted.commitment());
+    }
+
+    #[test]
+    fn canonical_encoding_separates_shifted_fields() {
+        let fields: [&[u8]; 2] = [b"27AA", b"BC"];
+        let shifted: [&[u8]; 2] = [b"27A", b"ABC"];
+
+        assert_eq!(fields.concat(), shifted.concat());
+        assert_ne!(canonical_encode(&fields), canonical_encode(&shifted));
+        assert_ne!(
+            keccak256(canonical_encode(&fields)),
+            keccak256(canonical_encode(&shifted))
+        );
+    }
+
+    #[test]
+    fn extracts_all_gstins_in_order() {
+        let page = "GSTIN 27AAPFU0939F1ZV Legal Name ACME TRADERS\n\
+                    Additional Place of Business 29AAGCB7383J1Z4\n\
+                    Principal Place 27AAPFU0939F1ZV";
+
+        assert_eq!(
+            extract_all_gstins(page),
+            vec!["27AAPFU0939F1ZV".to_string(), "29AAGCB7383J1Z4".to_string()]
+        );
+        assert!(extract_all_gstins("no registration here").is_empty());
+    }
+
+    #[test]
+    fn normalizes_name_whitespace_and_case() {
+        assert_eq!(
+            normalize_name("  Acme\u{00A0}\u{00A0}Traders \n Pvt.\u{200B} Ltd \n"),
+            "ACME TRADERS PVT. LTD"
+        );
+    }
+
+    #[test]
+    fn extracts_multi_line_address() {
+        let text = "4. Constitution of Business Proprietorship\n5. Address of Principal Place of Business\nNo. 12, MG  Road,\nBengaluru, Karnataka, 560001\n6. Date of Liability 01/07/2017";
+        let address = GST_ADDRESS_REGEX
+            .captures(text)
+            .and_then(|cap| cap.get(1))
+            .map(|m| normalize_address(m.as_str()));
+        assert_eq!(
+            address.as_deref(),
+            Some("No. 12, MG Road, Bengaluru, Karnataka, 560001")
+        );
+    }
+
+    #[test]
+    fn masks_all_but_last_four_characters() {
+        assert_eq!(mask_identifier("27AAPFU0939F1ZV"), "***********F1ZV");
+        assert_eq!(mask_identifier("ABCPE1234F"), "******234F");
+        assert_eq!(mask_identifier("1ZV"), "1ZV");
+    }
+
+    #[test]
+    fn maps_common_constitution_phrasings() {
+        assert_eq!(
+            parse_business_constitution("Proprietorship"),
+            BusinessConstitution::Proprietorship
+        );
+        assert_eq!(
+            parse_business_constitution("Pvt. Ltd."),
+            BusinessConstitution::PrivateLimitedCompany
+        );
+        assert_eq!(
+            parse_business_constitution("Limited Liability Partnership"),
+            BusinessConstitution::LimitedLiabilityPartnership
+        );
+        assert_eq!(
+            parse_business_constitution("Public Limited Company"),
+            BusinessConstitution::PublicLimitedCompany
+        );
+        assert_eq!(
+            parse_business_constitution("Hindu Undivided Family"),
+            BusinessConstitution::HinduUndividedFamily
+        );
+        assert_eq!(
+            parse_business_constitution("Foreign  Company"),
+            BusinessConstitution::Other("FOREIGN COMPANY".to_string())
+        );
+    }
+
+    /// Registration details as printed on a regular and a composition certificate
+    const REGULAR_GST_TEXT: &str = "GSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS\n\
+        Date of Liability 01/07/2017\nType of Registration Regular\n\
+        Particulars of Approving Authority\n";
+    const COMPOSITION_GST_TEXT: &str = "GSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS\n\
+        Date of Liability 01/07/2017\nType of Registration : Composition\n";
+
+    #[test]
+    fn reads_registration_type() {
+        let registration_type = |text: &str| {
+            let signature = PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            };
+            crate::extract_gst_certificate(
+                text,
+                signature,
+                None,
+                &crate::CertificateVerifier::default(),
+            )
+            .unwrap()
+            .certificate
+            .registration_type
+        };
+        assert_eq!(
+            registration_type(REGULAR_GST_TEXT),
+            Some(GstRegistrationType::Regular)
+        );
+        assert_eq!(
+            registration_type(COMPOSITION_GST_TEXT),
+            Some(GstRegistrationType::Composition)
+        );
+        assert_eq!(registration_type(BILINGUAL_GST_TEXT), None);
+
+        assert_eq!(
+            parse_registration_type("Casual Taxable Person"),
+            GstRegistrationType::CasualTaxable
+        );
+        assert_eq!(
+            parse_registration_type("Non-Resident Taxable Person"),
+            GstRegistrationType::NonResident
+        );
+        assert_eq!(
+            parse_registration_type("Input Service Distributor"),
+            GstRegistrationType::Other("INPUT SERVICE DISTRIBUTOR".to_string())
+        );
+    }
+
+    #[test]
+    fn gst_and_pan_error_messages_are_stable() {
+        let tampered = GSTVerificationError::from(TamperError::MalformedByteRange);
+        assert_eq!(
+            tampered.to_string(),
+            "PDF content is not covered by its signature: PDF signature ByteRange is malformed"
+        );
+        assert!(tampered.source().is_some());
+        assert_eq!(
+            GSTVerificationError::PdfTooLarge { size: 2, limit: 1 }.to_string(),
+            "PDF is 2 bytes, above the 1 byte limit"
+        );
+        assert_eq!(
+            PANVerificationError::UnknownCategory('X').to_string(),
+            "Unknown PAN holder category: X"
+        );
+        assert_eq!(
+            PANVerificationError::DOBNotFound.to_string(),
+            "DOB not found in PDF"
+        );
+    }
+
+    #[test]
+    fn annexure_pages_list_places_and_partners() {
+        let pages = [
+            "Form GST REG-06\nLegal Name ACME TRADERS\n11. Name\nSher Singh\n".to_string(),
+            "Annexure A\nGSTIN 07AAATC0869P1ZB\nLegal Name ACME TRADERS\n\
+             Details of Additional Places of Business(s)\n\
+             Total Number of Additional Places of Business(s) in the State 2\n\
+             Sr. No. Address\n1 Shop 4, Lane No.4,\nSaidulajab, New Delhi, 110030\n\
+             2. Plot 7, Okhla Phase II, Delhi\nPage 2 of 3\n"
+                .to_string(),
+            "Annexure B\nGSTIN 07AAATC0869P1ZB\n Legal Name ACME TRADERS\n\
+             Details of  Members of Managing Committee\n  1\n\
+             Name                  VIJAY  SINGH\nDesignation/Status  Assistant Director\n  2\n\
+             Name\nanita rao\nDigitally signed by DS GOODS AND SERVICES TAX NETWORK\n"
+                .to_string(),
+        ];
+
+        let annexure = extract_annexure(&pages).unwrap();
+        assert_eq!(
+            annexure.additional_places,
+            vec![
+                AddressEntry {
+                    serial: 1,
+                    address: "Shop 4, Lane No.4, Saidulajab, New Delhi, 110030".to_string(),
+                },
+                AddressEntry {
+                    serial: 2,
+                    address: "Plot 7, Okhla Phase II, Delhi".to_string(),
+                },
+            ]
+        );
+        assert_eq!(annexure.partners, vec!["VIJAY SINGH", "ANITA RAO"]);
+
+        assert_eq!(extract_annexure(&pages[..1]), None);
+    }
+
+    #[test]
+    fn gstin_category_follows_the_structure() {
+        let fixtures = [
+            ("27AAPFU0939F1ZV", GstinCategory::Standard),
+            ("0717USA00157UNS", GstinCategory::Uin),
+            ("27MUMA12345B1D5", GstinCategory::Tds),
+            ("9917USA29002OSF", GstinCategory::Oidar),
+        ];
+        for (gstin, category) in fixtures {
+            assert_eq!(gstin_category(gstin), Some(category), "{}", gstin);
+            assert!(validate_gstin_checksum(gstin), "{}", gstin);
+        }
+        assert_eq!(gstin_category("27AAPFU0939F1Z"), None);
+        assert_eq!(gstin_category("0717USA00157ZNS"), None);
+
+        let text = "UIN 0717USA00157UNS, TDS 27MUMA12345B1D5, GSTIN 27AAPFU0939F1ZV";
+        assert_eq!(
+            extract_special_gstins(text),
+            vec![
+                ("0717USA00157UNS".to_string(), GstinCategory::Uin),
+                ("27MUMA12345B1D5".to_string(), GstinCategory::Tds),
+            ]
+        );
+        assert_eq!(extract_all_gstins(text), vec!["27AAPFU0939F1ZV"]);
+    }
+
+    #[test]
+    fn special_gstins_are_flagged_unless_accepted() {
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let text = "UIN 0717USA00157UNS\nLegal Name EMBASSY OF ACME\n";
+        let mut warnings = Vec::new();
+        assert!(matches!(
+            crate::extract_gst_certificate_with_warnings(
+                text,
+                signature.clone(),
+                None,
+                &crate::CertificateVerifier::default(),
+                false,
+                &mut warnings,
+            ),
+            Err(GSTVerificationError::GSTNumberNotFound)
+        ));
+        assert!(warnings.contains(&ExtractionWarning::SpecialGstin {
+            gstin: "0717USA00157UNS".to_string(),
+            category: GstinCategory::Uin,
+            accepted: false,
+        }));
+
+        let accepting = crate::CertificateVerifier::builder()
+            .accept_special_gstins(true)
+            .build();
+        let mut warnings = Vec::new();
+        let extraction = crate::extract_gst_certificate_with_warnings(
+            text,
+            signature,
+            None,
+            &accepting,
+            false,
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(extraction.certificate.gst_number, "0717USA00157UNS");
+        assert_eq!(
+            extraction.certificate.gstin_category(),
+            Some(GstinCategory::Uin)
+        );
+        assert_eq!(extraction.certificate.state_code, 7);
+        assert!(warnings.contains(&ExtractionWarning::SpecialGstin {
+            gstin: "0717USA00157UNS".to_string(),
+            category: GstinCategory::Uin,
+            accepted: true,
+        }));
+    }
+
+    #[test]
+    fn fuzzy_mode_corrects_confusables_only_when_checksum_passes() {
+        assert_eq!(
+            correct_gstin_confusables("27AAPFUO939F1ZV").as_deref(),
+            Some("27AAPFU0939F1ZV")
+        );
+        assert_eq!(
+            correct_gstin_confusables("2IAAPFU0939F1ZV"),
+            None,
+            "21 is a valid state code but the checksum rules it out"
+        );
+        assert_eq!(correct_gstin_confusables("27AAPFUO939F1ZX"), None);
+
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let text = "GSTIN 27AAPFUO939F1ZV\nLegal Name ACME TRADERS\n";
+        assert!(matches!(
+            crate::extract_gst_certificate(
+                text,
+                signature.clone(),
+                None,
+                &crate::CertificateVerifier::default()
+            ),
+            Err(GSTVerificationError::GSTNumberNotFound)
+        ));
+
+        let fuzzy = crate::CertificateVerifier::builder().fuzzy(true).build();
+        let mut warnings = Vec::new();
+        let extraction = crate::extract_gst_certificate_with_warnings(
+            text,
+            signature,
+            None,
+            &fuzzy,
+            false,
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(extraction.certificate.gst_number, "27AAPFU0939F1ZV");
+        assert_eq!(
+            &text[extraction.gst_number_span.start..extraction.gst_number_span.end],
+            "27AAPFUO939F1ZV"
+        );
+        assert!(warnings.contains(&ExtractionWarning::ConfusablesCorrected {
+            printed: "27AAPFUO939F1ZV".to_string(),
+            corrected: "27AAPFU0939F1ZV".to_string(),
+        }));
+    }
+
+    #[test]
+    fn lists_every_matching_document_type() {
+        use crate::{detect_all_document_types, detect_document_type, DocumentType};
+
+        // The PAN embedded in a GSTIN does not make it a PAN card
+        let gst = "Goods and Services Tax\nGSTIN 27AAPFU0939F1ZV\n";
+        assert_eq!(detect_all_document_types(gst), [DocumentType::Gst]);
+
+        let kyc_pack = "GSTIN 27AAPFU0939F1ZV\nPermanent Account Number\nABCPK1234F\n";
+        assert_eq!(
+            detect_all_document_types(kyc_pack),
+            [DocumentType::Gst, DocumentType::Pan]
+        );
+        assert_eq!(detect_document_type(kyc_pack), DocumentType::Gst);
+
+        assert!(detect_all_document_types("no identifiers here").is_empty());
+        assert_eq!(
+            detect_document_type("no identifiers here"),
+            DocumentType::Unknown
+        );
+    }
+
+    #[test]
+    fn public_key_wraps_document_values() {
+        let bytes = encoded_gst_values(crate::PUBLIC_VALUES_SCHEMA_VERSION);
+        let public_key = vec![0x30, 0x82, 0x01, 0x0a];
+        let wrapped = crate::with_public_key(bytes.clone(), &public_key);
+
+        let Ok(crate::DecodedDocument::WithPublicKey(values)) =
+            crate::decode_public_values(&wrapped)
+        else {
+            panic!("wrapped values did not decode");
+        };
+        assert_eq!(values.public_key.as_ref(), public_key.as_slice());
+        assert_eq!(values.values.as_ref(), bytes.as_slice());
+        assert!(matches!(
+            crate::decode_public_values(&values.values),
+            Ok(crate::DecodedDocument::Gst(_))
+        ));
+    }
+
+    #[test]
+    fn try_decoders_return_none_instead_of_panicking() {
+        let bytes = encoded_gst_values(crate::PUBLIC_VALUES_SCHEMA_VERSION);
+        assert!(crate::try_decode_gst(&bytes).is_some());
+        assert!(crate::try_decode_pan(&bytes).is_none());
+        assert!(crate::try_decode_dl(&bytes).is_none());
+
+        assert!(crate::try_decode_gst(&bytes[..bytes.len() / 2]).is_none());
+        assert!(crate::try_decode_gst(&[]).is_none());
+        assert!(crate::DecodedDocument::try_from(&[0xff; 64][..]).is_err());
+    }
+
+    #[test]
+    fn full_document_commitment_covers_page_text() {
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let pages = |pages: &[&str]| {
+            pages
+                .iter()
+                .map(|page| page.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let original =
+            full_document_commitment(&pages(&["GSTIN 27AAPFU0939F1ZV", "Page 2"]), &signature);
+        assert_eq!(
+            original,
+            full_document_commitment(&pages(&["GSTIN  27AAPFU0939F1ZV\n", "Page 2"]), &signature)
+        );
+        assert_ne!(
+            original,
+            full_document_commitment(&pages(&["GSTIN 27AAPFU0939F1ZV", "Page 3"]), &signature)
+        );
+        assert_ne!(
+            original,
+            full_document_commitment(&pages(&["GSTIN 27AAPFU0939F1ZV Page 2"]), &signature)
+        );
+    }
+
+    fn encoded_gst_values(schema_version: u16) -> Vec<u8> {
+        use alloy_sol_types::SolType;
+
+        crate::GSTValuesStruct::abi_encode(&crate::GSTValuesStruct {
+            schema_version,
+            doc_kind: crate::CertificateKind::Gst as u8,
+            gst_number: "27AAPFU0939F1ZV".to_string(),
+            legal_name: "ACME TRADERS".to_string(),
+            trade_name: String::new(),
+            registration_timestamp: 0,
+            registration_type: String::new(),
+            signature_valid: true,
+            field_commitment: [3u8; 32].into(),
+            document_commitment: [1u8; 32].into(),
+            committed_match: false,
+            public_key_hash: [2u8; 32].into(),
+            proven_at: 0,
+        })
+    }
+
+    #[test]
+    fn decodes_current_schema_version() {
+        let bytes = encoded_gst_values(crate::PUBLIC_VALUES_SCHEMA_VERSION);
+        assert_eq!(
+            crate::public_values_schema_version(&bytes),
+            Some(crate::PUBLIC_VALUES_SCHEMA_VERSION)
+        );
+        assert_eq!(
+            crate::CertificateKind::from_public_values(&bytes),
+            Some(crate::CertificateKind::Gst)
+        );
+        assert!(matches!(
+            crate::decode_public_values(&bytes),
+            Ok(crate::DecodedDocument::Gst(values)) if values.gst_number == "27AAPFU0939F1ZV"
+        ));
+    }
+
+    #[test]
+    fn certificate_converts_to_values_struct() {
+        use alloy_sol_types::SolType;
+
+        let cert = GSTCertificate {
+            trade_name: Some("ACME".to_string()),
+            registration_date: NaiveDate::from_ymd_opt(2017, 7, 1),
+            ..gst()
+        };
+
+        let values = crate::GSTValuesStruct::from((&cert, [3u8; 32], [5u8; 32]));
+        let decoded =
+            crate::GSTValuesStruct::abi_decode(&crate::GSTValuesStruct::abi_encode(&values))
+                .unwrap();
+        assert_eq!(decoded.gst_number, cert.gst_number);
+        assert_eq!(decoded.legal_name, cert.legal_name);
+        assert_eq!(decoded.trade_name, "ACME");
+        assert_eq!(decoded.registration_timestamp, 1_498_867_200);
+        assert!(decoded.signature_valid);
+        assert_eq!(decoded.field_commitment.0, [3u8; 32]);
+        assert_eq!(decoded.public_key_hash.0, [5u8; 32]);
+    }
+
+    #[test]
+    fn gst_public_values_follow_disclosure_mask() {
+        let cert = gst();
+        let mut ctx = crate::CommitContext {
+            disclosure: DisclosureMask::REVEAL_ALL,
+            salt: [0u8; 32],
+            document_commitment: [4u8; 32],
+            committed_match: false,
+            include_public_key: false,
+            packed: false,
+            proven_at: 1_700_000_000,
+        };
+
+        let revealed = crate::gst_public_values(&cert, &ctx).unwrap();
+        assert!(matches!(
+            crate::decode_public_values(&revealed),
+            Ok(crate::DecodedDocument::Gst(values))
+                if values.gst_number == cert.gst_number
+                    && values.document_commitment.0 == [4u8; 32]
+                    && values.proven_at == ctx.proven_at
+        ));
+
+        ctx.disclosure.gst_number = false;
+        let masked = crate::gst_public_values(&cert, &ctx).unwrap();
+        assert!(matches!(
+            crate::decode_public_values(&masked),
+            Ok(crate::DecodedDocument::GstPrivate(values))
+                if values.gst_number.is_empty() && values.legal_name == cert.legal_name
+        ));
+    }
+
+    #[test]
+    fn hidden_field_commitments_are_salted() {
+        let cert = gst();
+        let ctx = |salt| crate::CommitContext {
+            disclosure: DisclosureMask {
+                gst_number: false,
+                legal_name: false,
+                ..DisclosureMask::REVEAL_ALL
+            },
+            salt,
+            document_commitment: [4u8; 32],
+            committed_match: false,
+            include_public_key: false,
+            packed: false,
+            proven_at: 1_700_000_000,
+        };
+        let commitment = |salt| {
+            let public_values = crate::gst_public_values(&cert, &ctx(salt)).unwrap();
+            let Ok(crate::DecodedDocument::GstPrivate(values)) =
+                crate::decode_public_values(&public_values)
+            else {
+                panic!("hidden GSTIN did not select the private layout");
+            };
+            (
+                values.gst_number_commitment.0,
+                values.legal_name_commitment.0,
+            )
+        };
+
+        let (gst_number, legal_name) = commitment([1u8; 32]);
+        assert_eq!(
+            gst_number,
+            salted_field_commitment([1u8; 32], b"gst_number", cert.gst_number.as_bytes())
+        );
+        assert_eq!(
+            legal_name,
+            salted_field_commitment([1u8; 32], b"legal_name", cert.legal_name.as_bytes())
+        );
+        assert_ne!(gst_number, keccak256(cert.gst_number.as_bytes()).0);
+        assert_ne!(legal_name, keccak256(cert.legal_name.as_bytes()).0);
+        assert_ne!(commitment([1u8; 32]), commitment([2u8; 32]));
+    }
+
+    #[test]
+    fn packed_gst_values_round_trip() {
+        let cert = GSTCertificate {
+            trade_name: Some("ACME".to_string()),
+            registration_date: NaiveDate::from_ymd_opt(2020, 1, 1),
+            ..gst()
+        };
+        let mut ctx = crate::CommitContext {
+            disclosure: DisclosureMask::REVEAL_ALL,
+            salt: [7u8; 32],
+            document_commitment: [4u8; 32],
+            committed_match: true,
+            include_public_key: false,
+            packed: false,
+            proven_at: 1_700_000_000,
+        };
+        let verbose = crate::gst_public_values(&cert, &ctx).unwrap();
+
+        ctx.packed = true;
+        let packed = crate::gst_public_values(&cert, &ctx).unwrap();
+        assert_eq!(packed.len(), 384);
+        assert!(packed.len() < verbose.len());
+
+        let values = crate::try_decode_gst_packed(&packed).unwrap();
+        assert_eq!(values.gstin(), cert.gst_number);
+        assert_eq!(values.state_code, 27);
+        assert_eq!(
+            values.legal_name_commitment.0,
+            salted_field_commitment(ctx.salt, b"legal_name", cert.legal_name.as_bytes())
+        );
+        assert_ne!(
+            values.legal_name_commitment.0,
+            keccak256(cert.legal_name.as_bytes()).0
+        );
+        assert_eq!(values.registration_timestamp, 1_577_836_800);
+        assert!(values.signature_valid && values.committed_match);
+        assert_eq!(values.proven_at, 1_700_000_000);
+
+        ctx.disclosure.gst_number = false;
+        let hidden = crate::gst_public_values(&cert, &ctx).unwrap();
+        assert_eq!(crate::try_decode_gst_packed(&hidden).unwrap().gstin(), "");
+    }
+
+    #[test]
+    fn encrypted_pdfs_are_rejected_as_encrypted() {
+        let encrypted = b"%PDF-1.7\ntrailer\n<< /Size 3 /Root 1 0 R /Encrypt 2 0 R >>\n%%EOF";
+        assert!(is_encrypted_pdf(encrypted));
+        assert!(!is_encrypted_pdf(
+            b"%PDF-1.7\n<< /EncryptMetadata false >>\ntrailer\n<< /Root 1 0 R >>"
+        ));
+        assert!(matches!(
+            crate::verify_gst_certificate(encrypted.to_vec()),
+            Err(GSTVerificationError::EncryptedPdf)
+        ));
+        assert!(matches!(
+            crate::verify_pan_certificate(encrypted.to_vec()),
+            Err(PANVerificationError::EncryptedPdf)
+        ));
+        assert!(matches!(
+            crate::verify_driving_license(encrypted.to_vec()),
+            Err(DLVerificationError::EncryptedPdf)
+        ));
+        assert!(matches!(
+            crate::CertificateVerifier::default().verify_document(encrypted.to_vec()),
+            Err(DocumentVerificationError::EncryptedPdf)
+        ));
+    }
+
+    #[test]
+    fn blank_pages_have_no_extractable_text() {
+        assert!(!has_extractable_text(&[]));
+        assert!(!has_extractable_text(&[String::new(), " \n\t".to_string()]));
+        assert!(has_extractable_text(&[String::new(), "GSTIN".to_string()]));
+    }
+
+    #[test]
+    fn decodes_bundle_public_values() {
+        let bytes =
+            crate::bundle_public_values(&[], &DisclosureMask::REVEAL_ALL, [0u8; 32], 7).unwrap();
+        assert_eq!(
+            crate::CertificateKind::from_public_values(&bytes),
+            Some(crate::CertificateKind::Bundle)
+        );
+        assert!(matches!(
+            crate::decode_public_values(&bytes),
+            Ok(crate::DecodedDocument::Bundle(values))
+                if values.doc_kinds.is_empty() && values.all_signatures_valid && values.proven_at == 7
+        ));
+    }
+
+    #[test]
+    fn default_stop_labels_keep_name_patterns() {
+        assert_eq!(
+            GST_LEGAL_NAME_REGEX.as_str(),
+            r"Legal Name\s*([\p{L}\p{M}\s&.,/]+?)(?:\n|Trade Name|Additional|$)"
+        );
+        assert_eq!(
+            PAN_NAME_VALUE_REGEX.as_str(),
+            r"^\s*([A-Za-z\s&.,]+?)(?:\n|Father|DOB|Date of Birth|$)"
+        );
+    }
+
+    #[test]
+    fn extra_stop_label_ends_legal_name() {
+        let text = "Legal Name ACME TRADERS Status Active";
+        let name = |regex: &Regex| {
+            regex
+                .captures(text)
+                .and_then(|cap| cap.get(1))
+                .map(|m| normalize_name(m.as_str()))
+        };
+        assert_eq!(
+            name(&GST_LEGAL_NAME_REGEX).as_deref(),
+            Some("ACME TRADERS STATUS ACTIVE")
+        );
+
+        let mut config = GstConfig::default();
+        config.stop_labels.push("Status".to_string());
+        assert_eq!(
+            name(&config.legal_name_regex()).as_deref(),
+            Some("ACME TRADERS")
+        );
+    }
+
+    /// A minimal file whose ByteRange excludes only the `<...>` signature value
+    fn signed_pdf(signature: &str) -> Vec<u8> {
+        sign_update(b"%PDF-1.7\n".to_vec(), signature)
+    }
+
+    /// Appends a signature dictionary whose ByteRange covers `pdf` and the dictionary,
+    /// excluding only the `<...>` signature value
+    fn sign_update(pdf: Vec<u8>, signature: &str) -> Vec<u8> {
+        let head = "/ByteRange [0000000000 0000000000 0000000000 0000000000] /Contents ";
+        let tail = " >>\n%%EOF\n";
+        let gap_start = pdf.len() + head.len();
+        let gap_end = gap_start + signature.len();
+        let byte_range = format!(
+            "{:010} {:010} {:010} {:010}",
+            0,
+            gap_start,
+            gap_end,
+            tail.len()
+        );
+        pdf.into_iter()
+            .chain(
+                head.replace("0000000000 0000000000 0000000000 0000000000", &byte_range)
+                    .into_bytes(),
+            )
+            .chain(signature.bytes())
+            .chain(tail.bytes())
+            .collect()
+    }
+
+    #[test]
+    fn signature_must_cover_whole_file() {
+        let pdf = signed_pdf("<3082ab00>");
+        assert_eq!(assert_text_within_signed_range(&pdf), Ok(()));
+
+        let mut appended = pdf.clone();
+        appended.extend_from_slice(b"BT (FORGED NAME) Tj ET\n%%EOF\n");
+        assert!(matches!(
+            assert_text_within_signed_range(&appended),
+            Err(TamperError::UnsignedTrailingBytes { .. })
+        ));
+
+        assert_eq!(
+            assert_text_within_signed_range(&signed_pdf("<30(FORGED)>")),
+            Err(TamperError::UnsignedGap)
+        );
+        assert_eq!(
+            assert_text_within_signed_range(b"%PDF-1.7\n%%EOF\n"),
+            Err(TamperError::ByteRangeNotFound)
+        );
+    }
+
+    #[test]
+    fn appended_signature_covering_the_whole_file_is_rejected() {
+        // zkpdf would still verify the original signature, which does not cover the update
+        let mut update = signed_pdf("<3082ab00>");
+        update.extend_from_slice(b"BT (FORGED NAME) Tj ET\n");
+        let forged = sign_update(update, "<3082cd00>");
+
+        assert_eq!(
+            assert_text_within_signed_range(&forged),
+            Err(TamperError::MultipleSignatures(2))
+        );
+    }
+
+    #[test]
+    fn extracts_identifiers_from_plain_text() {
+        use crate::extract::{extract_gstin, extract_pan};
+
+        let text = "GSTIN 27AAPFU0939F1ZA (typo) then 27AAPFU0939F1ZV";
+        assert_eq!(extract_gstin(text).as_deref(), Some("27AAPFU0939F1ZV"));
+        assert_eq!(extract_gstin("no identifiers here"), None);
+
+        assert_eq!(
+            extract_pan("Ref ABCDX1234E PAN ABCPE1234F").as_deref(),
+            Some("ABCPE1234F")
+        );
+        assert_eq!(extract_pan("Ref ABCDX1234E"), None);
+    }
+
+    #[test]
+    fn reads_der_validity_times() {
+        assert_eq!(
+            der_time_date(0x17, b"240101000000Z"),
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+        );
+        assert_eq!(
+            der_time_date(0x17, b"991231235959Z"),
+            NaiveDate::from_ymd_opt(1999, 12, 31)
+        );
+        assert_eq!(
+            der_time_date(0x18, b"20501231235959Z"),
+            NaiveDate::from_ymd_opt(2050, 12, 31)
+        );
+        assert_eq!(der_time_date(0x17, b"241301000000Z"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_schema_version() {
+        let bytes = encoded_gst_values(crate::PUBLIC_VALUES_SCHEMA_VERSION + 1);
+        assert!(matches!(
+            crate::decode_public_values(&bytes),
+            Err(PublicValuesError::UnsupportedVersion(version))
+                if version == crate::PUBLIC_VALUES_SCHEMA_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn reads_only_last_four_aadhaar_digits() {
+        let text = "Rahul Kumar\nDOB: 15/08/1990\nMALE\nXXXX XXXX 1234";
+        let last4 = AADHAAR_MASKED_UID_REGEX
+            .captures(text)
+            .and_then(|cap| cap.get(1))
+            .map(|m| m.as_str());
+        assert_eq!(last4, Some("1234"));
+        assert!(!AADHAAR_MASKED_UID_REGEX.is_match("1234 5678 9012"));
+
+        let name = AADHAAR_NAME_REGEX
+            .captures(text)
+            .and_then(|cap| cap.get(1))
+            .map(|m| normalize_name(m.as_str()));
+        assert_eq!(name.as_deref(), Some("RAHUL KUMAR"));
+
+        let gender = GENDER_REGEX
+            .captures(text)
+            .and_then(|cap| cap.get(1))
+            .and_then(|m| parse_gender(m.as_str()));
+        assert_eq!(gender, Some(Gender::Male));
+        assert_eq!(
+            GENDER_REGEX
+                .captures("FEMALE")
+                .and_then(|cap| parse_gender(&cap[1])),
+            Some(Gender::Female)
+        );
+    }
+
+    #[test]
+    fn reads_pan_print_date_when_present() {
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let extract = |text: &str| {
+            crate::extract_pan_certificate(
+                text,
+                signature.clone(),
+                None,
+                &crate::CertificateVerifier::default(),
+            )
+            .unwrap()
+        };
+
+        let printed = extract(
+            "INCOME TAX DEPARTMENT\nDate of Issue: 02/01/2024\nName RAHUL KUMAR\nABCPE1234F\nDOB 15/08/1990",
+        );
+        assert_eq!(printed.print_date, NaiveDate::from_ymd_opt(2024, 1, 2));
+        assert_eq!(printed.dob, "15/08/1990");
+        let values = crate::PANValuesStruct::from((&printed, [0; 32], [0; 32]));
+        assert_eq!(values.print_timestamp, 1_704_153_600);
+
+        let unprinted =
+            extract("INCOME TAX DEPARTMENT\nName RAHUL KUMAR\nABCPE1234F\nDOB 15/08/1990");
+        assert_eq!(unprinted.print_date, None);
+        let values = crate::PANValuesStruct::from((&unprinted, [0; 32], [0; 32]));
+        assert_eq!(values.print_timestamp, 0);
+    }
+
+    #[test]
+    fn redacts_identifiers_down_to_a_suffix() {
+        use crate::extract::{redact_sensitive, redact_sensitive_with};
+
+        let text = "GSTIN 27AAPFU0939F1ZV\nPAN ABCPE1234F\nDOB 15/08/1990\nXXXX XXXX 1234";
+        assert_eq!(
+            redact_sensitive(text),
+            "GSTIN ***********F1ZV\nPAN ******234F\nDOB **/**/1990\n**** **** 1234"
+        );
+        assert_eq!(
+            redact_sensitive_with("Year of Birth: 1990 ABCPE1234F", 2),
+            "Year of Birth: **90 ********4F"
+        );
+        assert_eq!(redact_sensitive_with("ABCPE1234F", 0), "**********");
+        assert_eq!(redact_sensitive("Name RAHUL KUMAR"), "Name RAHUL KUMAR");
+    }
+
+    #[test]
+    fn tells_signature_failures_from_parse_failures() {
+        assert!(is_signature_failure("Signature is invalid"));
+        assert!(is_signature_failure("message digest mismatch"));
+        assert!(is_signature_failure(
+            "Signature verification failed: signed attributes do not match, hash Does Not Match"
+        ));
+        assert!(!is_signature_failure("Failed to parse PDF: unexpected EOF"));
+        assert!(!is_signature_failure("No signature found in PDF"));
+        assert!(!is_signature_failure("invalid xref table"));
+
+        assert!(is_parse_failure("Failed to parse PDF: unexpected EOF"));
+        assert!(is_parse_failure("invalid xref table"));
+        assert!(!is_parse_failure("No signature found in PDF"));
+        assert!(!is_parse_failure("Signature is invalid"));
+    }
+
+    #[test]
+    fn every_document_kind_classifies_zkpdf_failures() {
+        let failed = |message: &str| SignedPdfError::VerificationFailed(message.to_string());
+
+        assert!(matches!(
+            PANVerificationError::from(failed("Signature is invalid")),
+            PANVerificationError::SignatureInvalid
+        ));
+        assert!(matches!(
+            DLVerificationError::from(failed("invalid xref table")),
+            DLVerificationError::PdfParseFailed(_)
+        ));
+        assert!(matches!(
+            AadhaarVerificationError::from(failed("message digest mismatch")),
+            AadhaarVerificationError::SignatureInvalid
+        ));
+        assert!(matches!(
+            DocumentVerificationError::from(SignedPdfError::Tampered(
+                TamperError::ByteRangeNotFound
+            )),
+            DocumentVerificationError::Tampered(TamperError::ByteRangeNotFound)
+        ));
+        assert!(matches!(
+            DocumentVerificationError::from(failed("No signature found in PDF")),
+            DocumentVerificationError::PdfVerificationFailed(_)
+        ));
+    }
+
+    #[test]
+    fn reads_contact_details_only_when_enabled() {
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let text = "GSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS\nEmail Id: ac****@example.co.in\nMobile No. +91 XXXXXX3210";
+        let extract = |verifier: &crate::CertificateVerifier| {
+            crate::extract_gst_certificate(text, signature.clone(), None, verifier)
+                .unwrap()
+                .certificate
+        };
+
+        let cert = extract(&crate::CertificateVerifier::default());
+        assert_eq!((cert.email, cert.mobile_last4), (None, None));
+
+        let cert = extract(
+            &crate::CertificateVerifier::builder()
+                .extract_contact(true)
+                .build(),
+        );
+        assert_eq!(cert.email.as_deref(), Some("ac****@example.co.in"));
+        assert_eq!(cert.mobile_last4.as_deref(), Some("3210"));
+
+        let unmasked = GST_MOBILE_LAST4_REGEX
+            .captures("Mobile: 9876543210")
+            .unwrap();
+        assert_eq!(&unmasked[1], "3210");
+    }
+
+    #[test]
+    fn extracts_from_already_verified_content() {
+        let content = |pages: &[&str]| zkpdf_lib::PdfVerifiedContent {
+            pages: pages.iter().map(|page| page.to_string()).collect(),
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+        };
+
+        let gst = crate::gst_from_verified(content(&[
+            "GSTIN 27AAPFU0939F1ZV",
+            "Legal Name ACME TRADERS",
+        ]))
+        .unwrap();
+        assert_eq!(gst.gst_number, "27AAPFU0939F1ZV");
+        assert!(matches!(
+            crate::gst_from_verified(content(&[" ", ""])),
+            Err(GSTVerificationError::NoExtractableText)
+        ));
+
+        let pan =
+            crate::pan_from_verified(content(&["Name RAHUL KUMAR\nABCPE1234F\nDOB 15/08/1990"]))
+                .unwrap();
+        assert_eq!(pan.pan_number, "ABCPE1234F");
+    }
+
+    /// Only a GST sample is bundled, see `tests/samples.rs`; the other layouts are fed
+    /// as verified text
+    #[test]
+    fn unified_entry_point_returns_the_detected_certificate() {
+        let content = |text: &str| zkpdf_lib::PdfVerifiedContent {
+            pages: vec![text.to_string()],
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+        };
+
+        assert!(matches!(
+            crate::certificate_from_verified(content(
+                "Goods and Services Tax\nGSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS"
+            )),
+            Ok(crate::CertificateOutcome::Gst(cert)) if cert.gst_number == "27AAPFU0939F1ZV"
+        ));
+        assert!(matches!(
+            crate::certificate_from_verified(content(
+                "Permanent Account Number\nName RAHUL KUMAR\nABCPE1234F\nDOB 15/08/1990"
+            )),
+            Ok(crate::CertificateOutcome::Pan(cert)) if cert.pan_number == "ABCPE1234F"
+        ));
+        assert!(matches!(
+            crate::certificate_from_verified(content(
+                "Driving Licence\nMH12 20110012345\nName: RAHUL KUMAR\nIssuing Authority: RTO PUNE\nClass of Vehicle: LMV\nValid Till: 01/01/2030"
+            )),
+            Ok(crate::CertificateOutcome::Dl(cert)) if cert.holder_name == "RAHUL KUMAR"
+        ));
+        assert!(matches!(
+            crate::certificate_from_verified(content("Nothing to see here")),
+            Err(DocumentVerificationError::NoDocumentMatched)
+        ));
+        assert!(matches!(
+            crate::verify_certificate(vec![0; MAX_PDF_BYTES + 1]),
+            Err(DocumentVerificationError::PdfTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn weak_digests_are_rejected_only_when_asked() {
+        let content = |digest_len: usize| zkpdf_lib::PdfVerifiedContent {
+            pages: vec!["GSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS".to_string()],
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; digest_len],
+                public_key: vec![2; 64],
+            },
+        };
+        let strict = crate::CertificateVerifier::builder()
+            .reject_weak_digests(true)
+            .build();
+
+        assert_eq!(digest_algorithm(&content(16).signature), DigestAlgo::Md5);
+        assert_eq!(digest_algorithm(&content(48).signature), DigestAlgo::Sha384);
+        assert_eq!(digest_algorithm(&content(0).signature), DigestAlgo::Unknown);
+
+        let Ok(document) =
+            crate::CertificateVerifier::default().document_from_verified(content(20))
+        else {
+            panic!("SHA-1 signature rejected by the default verifier");
+        };
+        let crate::CertificateOutcome::Gst(cert) = document.certificate else {
+            panic!("not read as GST");
+        };
+        assert_eq!(cert.signer.digest_algorithm, DigestAlgo::Sha1);
+
+        assert!(matches!(
+            strict.document_from_verified(content(20)),
+            Err(DocumentVerificationError::WeakDigest(DigestAlgo::Sha1))
+        ));
+        assert!(strict.document_from_verified(content(32)).is_ok());
+    }
+
+    #[test]
+    fn oversized_text_is_truncated_before_scanning() {
+        assert_eq!(truncate_scan_text("ab\u{e9}cd", 3), ("ab\u{e9}", true));
+        assert_eq!(truncate_scan_text("abc", 3), ("abc", false));
+
+        let text = format!(
+            "GSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS\n{}",
+            "GSTIN 29AAPFU0939F1ZR ".repeat(100_000)
+        );
+        let verifier = crate::CertificateVerifier::builder()
+            .max_scan_chars(1_000)
+            .build();
+        let mut warnings = Vec::new();
+        let extraction = crate::extract_gst_certificate_with_warnings(
+            &text,
+            PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+            None,
+            &verifier,
+            false,
+            &mut warnings,
+        )
+        .unwrap();
+
+        assert_eq!(extraction.certificate.gst_number, "27AAPFU0939F1ZV");
+        assert_eq!(extraction.certificate.legal_name, "ACME TRADERS");
+        assert!(warnings.contains(&ExtractionWarning::TextTruncated { max_chars: 1_000 }));
+        assert!(extraction.certificate.additional_gst_numbers.len() < 100);
+
+        let pan = format!(
+            "Name RAHUL KUMAR\nABCPE1234F\nDOB 15/08/1990\n{}",
+            "x".repeat(2_000)
+        );
+        let document = verifier
+            .document_from_verified(zkpdf_lib::PdfVerifiedContent {
+                pages: vec![pan],
+                signature: PdfSignatureResult {
+                    is_valid: true,
+                    message_digest: vec![1; 32],
+                    public_key: vec![2; 64],
+                },
+            })
+            .unwrap();
+        assert_eq!(
+            document.warnings,
+            [ExtractionWarning::TextTruncated { max_chars: 1_000 }]
+        );
+    }
+
+    #[test]
+    fn name_variants_strip_legal_form_suffixes() {
+        assert_eq!(
+            name_variants("Acme Traders Pvt. Ltd."),
+            ["ACME TRADERS PVT. LTD.", "ACME TRADERS"]
+        );
+        assert_eq!(
+            name_variants("ACME INDIA PRIVATE LIMITED"),
+            ["ACME INDIA PRIVATE LIMITED", "ACME INDIA"]
+        );
+        assert_eq!(
+            name_variants("Acme Consulting, LLP"),
+            ["ACME CONSULTING, LLP", "ACME CONSULTING"]
+        );
+        assert_eq!(name_variants("ACME TRADERS"), ["ACME TRADERS"]);
+        // A name that is only a suffix keeps just its full form
+        assert_eq!(name_variants("LIMITED"), ["LIMITED"]);
+        assert!(name_variants("  ").is_empty());
+    }
+
+    #[test]
+    fn reads_labelled_pan_gender() {
+        let gender = |text: &str| {
+            PAN_GENDER_REGEX
+                .captures(text)
+                .and_then(|cap| parse_gender(&cap[1]))
+        };
+        assert_eq!(
+            gender("Gender: Female\nDOB 15/08/1990"),
+            Some(Gender::Female)
+        );
+        assert_eq!(gender("Sex / M"), Some(Gender::Male));
+        assert_eq!(gender("Name RAHUL KUMAR DOB 15/08/1990"), None);
+        assert_eq!(
+            gender_from_code(gender_code(Some(Gender::Other))),
+            Some(Gender::Other)
+        );
+        assert_eq!(gender_from_code(GENDER_WITHHELD), None);
+    }
+
+    #[test]
+    fn lenient_extraction_warns_on_missing_legal_name() {
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let text = "GSTIN 27AAPFU0939F1ZV\nAdditional Place of Business 29AAGCB7383J1Z4";
+
+        let mut warnings = Vec::new();
+        let extraction = crate::extract_gst_certificate_with_warnings(
+            text,
+            signature.clone(),
+            None,
+            &crate::CertificateVerifier::default(),
+            true,
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(extraction.certificate.gst_number, "27AAPFU0939F1ZV");
+        assert_eq!(extraction.certificate.legal_name, "");
+        assert!(warnings.contains(&ExtractionWarning::MissingLegalName));
+        assert!(warnings.contains(&ExtractionWarning::MultipleGstinsFound(2)));
+
+        assert!(matches!(
+            crate::extract_gst_certificate(
+                text,
+                signature,
+                None,
+                &crate::CertificateVerifier::default()
+            ),
+            Err(GSTVerificationError::LegalNameNotFound)
+        ));
+    }
+
+    /// Legal name line of a certificate printing each label and value in Hindi too
+    const BILINGUAL_GST_TEXT: &str = "GSTIN / जीएसटीआईएन 27AAPFU0939F1ZV\n\
+        Legal Name / विधिक नाम ACME TRADERS / एक्मे ट्रेडर्स\n\
+        Trade Name, if any / व्यापार नाम ACME\n";
+
+    #[test]
+    fn extracts_english_name_from_bilingual_line() {
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let certificate = crate::extract_gst_certificate(
+            BILINGUAL_GST_TEXT,
+            signature,
+            None,
+            &crate::CertificateVerifier::default(),
+        )
+        .unwrap();
+        assert_eq!(certificate.certificate.legal_name, "ACME TRADERS");
+
+        assert_eq!(
+            normalize_bilingual_name(" M/S Acme & Sons "),
+            "M/S ACME & SONS"
+        );
+        assert_eq!(normalize_bilingual_name("एक्मे ट्रेडर्स"), "एक्मे ट्रेडर्स");
+    }
+
+    #[test]
+    fn metadata_leaves_out_identifying_fields() {
+        let signature = PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: vec![2; 64],
+        };
+        let certificate = crate::extract_gst_certificate(
+            BILINGUAL_GST_TEXT,
+            signature,
+            None,
+            &crate::CertificateVerifier::default(),
+        )
+        .unwrap()
+        .certificate;
+
+        let metadata = crate::certificate_metadata(&certificate).with_page_count(1);
+        assert_eq!(metadata.kind, crate::CertificateKind::Gst);
+        assert!(metadata.signature_valid);
+        assert_eq!(metadata.page_count, Some(1));
+
+        let logged = format!("{:?}", metadata);
+        assert!(!logged.contains(&certificate.gst_number));
+        assert!(!logged.contains(&certificate.legal_name));
+    }
+
+    #[test]
+    fn proves_each_age_threshold_separately() {
+        // 19 years old on the as-of date
+        let dob = parse_indian_dob("15/08/2005").unwrap();
+        let as_of = NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+
+        let over: Vec<bool> = [18, 21]
+            .iter()
+            .map(|threshold| prove_age_threshold(&dob, *threshold, as_of))
+            .collect();
+        assert_eq!(over, [true, false]);
+
+        // Not yet 19 the day before the birthday
+        let before_birthday = NaiveDate::from_ymd_opt(2024, 8, 14).unwrap();
+        assert!(!prove_age_threshold(&dob, 19, before_birthday));
+    }
+
+    #[test]
+    fn parses_each_dob_format() {
+        let expected = NaiveDate::from_ymd_opt(1990, 8, 15).unwrap();
+
+        assert_eq!(parse_indian_dob("15/08/1990").unwrap(), expected);
+        assert_eq!(parse_indian_dob("15-08-1990").unwrap(), expected);
+        assert_eq!(parse_indian_dob("1990-08-15").unwrap(), expected);
+    }
+
+    #[test]
+    fn rejects_malformed_dob() {
+        assert!(parse_indian_dob("31/02/1990").is_err());
+        assert!(parse_indian_dob("15.08.1990").is_err());
+    }
+
+    #[test]
+    fn commitments_equal_compares_every_byte() {
+        let a = [7u8; 32];
+        let mut b = a;
+        assert!(commitments_equal(&a, &b));
+
+        b[31] ^= 1;
+        assert!(!commitments_equal(&a, &b));
+
+        b = a;
+        b[0] ^= 0x80;
+        assert!(!commitments_equal(&a, &b));
+    }
+
+    fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+        assert!(content.len() < 0x80);
+        [&[tag, content.len() as u8][..], content].concat()
+    }
+
+    #[test]
+    fn reads_signer_common_name_from_certificate() {
+        let common_name = der(
+            0x31,
+            &der(
+                0x30,
+                &[der(0x06, OID_COMMON_NAME), der(0x0c, b"NSDL e-Governance")].concat(),
+            ),
+        );
+        let country = der(
+            0x31,
+            &der(
+                0x30,
+                &[der(0x06, &[0x55, 0x04, 0x06]), der(0x13, b"IN")].concat(),
+            ),
+        );
+        let subject = der(0x30, &[country, common_name].concat());
+        let tbs = der(
+            0x30,
+            &[
+                der(0xa0, &der(0x02, &[2])),
+                der(0x02, &[1]),
+                der(0x30, &[]),
+                der(0x30, &[]),
+                der(0x30, &[]),
+                subject,
+            ]
+            .concat(),
+        );
+        let certificate = der(0x30, &[tbs, der(0x30, &[]), der(0x03, &[0])].concat());
+
+        assert_eq!(
+            certificate_common_name(&certificate).as_deref(),
+            Some("NSDL e-Governance")
+        );
+    }
+
+    #[test]
+    fn gst_sample_embeds_the_certificate_of_the_key_zkpdf_verifies() {
+        let certificates =
+            embedded_certificates(include_bytes!("../../samples/GST-certificate.pdf"));
+        let [certificate] = certificates.as_slice() else {
+            panic!(
+                "expected only the signer certificate, got {}",
+                certificates.len()
+            );
+        };
+
+        // The key bits hash to the GSTN key hash pinned from zkpdf's `public_key`
+        let (_, key) = certificate_public_key(certificate).unwrap();
+        assert_eq!(keccak256(key).0, KNOWN_ISSUER_KEY_HASHES[0]);
+        let signature = |public_key: Vec<u8>| PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 20],
+            public_key,
+        };
+        assert_eq!(
+            signer_certificate(&certificates, &signature(key.to_vec())),
+            Some(certificate.as_slice())
+        );
+        assert_eq!(
+            signer_certificate(&certificates, &signature(vec![2; 64])),
+            None
+        );
+
+        assert_eq!(
+            signer_common_name(certificate).as_deref(),
+            Some("DS GOODS AND SERVICES TAX NETWORK 07")
+        );
+        assert_eq!(
+            signer_cert_validity(certificate),
+            Some((
+                NaiveDate::from_ymd_opt(2022, 9, 30).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 9, 30).unwrap()
+            ))
+        );
+        assert!(!signer_is_self_signed(certificate));
+    }
+
+    #[test]
+    fn a_certificate_naming_uidai_is_not_a_uidai_signer() {
+        let subject = der(
+            0x30,
+            &der(
+                0x31,
+                &der(
+                    0x30,
+                    &[
+                        der(0x06, OID_COMMON_NAME),
+                        der(0x0c, b"Unique Identification Authority of India"),
+                    ]
+                    .concat(),
+                ),
+            ),
+        );
+        let tbs = der(
+            0x30,
+            &[
+                der(0x02, &[1]),
+                der(0x30, &[]),
+                subject.clone(),
+                der(0x30, &[]),
+                subject,
+            ]
+            .concat(),
+        );
+        let certificate = der(0x30, &[tbs, der(0x30, &[]), der(0x03, &[0])].concat());
+
+        assert!(!is_uidai_signer(&PdfSignatureResult {
+            is_valid: true,
+            message_digest: vec![1; 32],
+            public_key: certificate,
+        }));
+    }
+
+    #[test]
+    fn self_signed_signers_are_flagged_and_rejected_when_asked() {
+        let name = |common_name: &[u8]| {
+            der(
+                0x30,
+                &der(
+                    0x31,
+                    &der(
+                        0x30,
+                        &[der(0x06, OID_COMMON_NAME), der(0x0c, common_name)].concat(),
+                    ),
+                ),
+            )
+        };
+        let certificate = |issuer: Vec<u8>| {
+            let tbs = der(
+                0x30,
+                &[
+                    der(0x02, &[1]),
+                    der(0x30, &[]),
+                    issuer,
+                    der(0x30, &[]),
+                    name(b"ACME Signer"),
+                ]
+                .concat(),
+            );
+            der(0x30, &[tbs, der(0x30, &[]), der(0x03, &[0])].concat())
+        };
+        let content = || zkpdf_lib::PdfVerifiedContent {
+            pages: vec!["GSTIN 27AAPFU0939F1ZV\nLegal Name ACME TRADERS".to_string()],
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+        };
+        let self_signed = certificate(name(b"ACME Signer"));
+        let issued = certificate(name(b"ACME CA"));
+
+        assert!(signer_is_self_signed(&self_signed));
+        assert!(!signer_is_self_signed(&issued));
+        assert!(!signer_is_self_signed(&[2; 64]));
+
+        let Ok(document) = crate::CertificateVerifier::default()
+            .document_from_signed(content(), Some(&self_signed))
+        else {
+            panic!("self-signed signer rejected by the default verifier");
+        };
+        let crate::CertificateOutcome::Gst(cert) = document.certificate else {
+            panic!("not read as GST");
+        };
+        assert!(cert.signer.self_signed);
+        assert!(crate::certificate_metadata(&cert).signer.self_signed);
+
+        let strict = crate::CertificateVerifier::builder()
+            .reject_self_signed(true)
+            .build();
+        assert!(matches!(
+            strict.document_from_signed(content(), Some(&self_signed)),
+            Err(DocumentVerificationError::SelfSignedSigner)
+        ));
+        assert!(strict
+            .document_from_signed(content(), Some(&issued))
+            .is_ok());
+        // Without the certificate nothing shows the signer is not self-signed
+        assert!(matches!(
+            strict.document_from_verified(content()),
+            Err(DocumentVerificationError::SignerCertificateUnavailable)
+        ));
+    }
+
+    #[test]
+    fn bare_public_key_has_no_common_name() {
+        let spki = der(
+            0x30,
+            &[der(0x30, &der(0x06, OID_ED25519)), der(0x03, &[0; 33])].concat(),
+        );
+        assert_eq!(certificate_common_name(&spki), None);
+    }
+
+    #[test]
+    fn trusted_issuer_must_be_listed() {
+        let gstn = KNOWN_ISSUER_KEY_HASHES[0];
+        assert!(is_trusted_issuer(&gstn, KNOWN_ISSUER_KEY_HASHES));
+        assert!(!is_trusted_issuer(&[0u8; 32], KNOWN_ISSUER_KEY_HASHES));
+        assert!(!is_trusted_issuer(&gstn, &[]));
+    }
+
+    #[test]
+    fn trusted_issuers_apply_to_every_document_kind() {
+        let content = || zkpdf_lib::PdfVerifiedContent {
+            pages: vec!["Name RAHUL KUMAR\nABCPE1234F\nDOB 15/08/1990".to_string()],
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+        };
+        let verifier = |allowlist: &[[u8; 32]]| {
+            crate::CertificateVerifier::builder()
+                .trusted_issuers(allowlist.to_vec())
+                .build()
+        };
+
+        // No NSDL key is pinned, so a PAN fails closed against the known issuers
+        assert!(matches!(
+            verifier(KNOWN_ISSUER_KEY_HASHES).document_from_verified(content()),
+            Err(DocumentVerificationError::UntrustedIssuer)
+        ));
+        let signer: [u8; 32] = keccak256([2u8; 64]).into();
+        assert!(matches!(
+            verifier(&[signer]).document_from_verified(content()),
+            Ok(crate::VerifiedDocument {
+                certificate: crate::CertificateOutcome::Pan(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn aadhaar_is_rejected_until_enabled_and_pinned() {
+        let content = zkpdf_lib::PdfVerifiedContent {
+            pages: vec!["Rahul Kumar\nDOB: 15/08/1990\nMALE\nXXXX XXXX 1234".to_string()],
+            signature: PdfSignatureResult {
+                is_valid: true,
+                message_digest: vec![1; 32],
+                public_key: vec![2; 64],
+            },
+        };
+        let result = crate::CertificateVerifier::builder()
+            .document_type(crate::DocumentType::Aadhaar)
+            .build()
+            .document_from_verified(content);
+
+        if cfg!(feature = "aadhaar") {
+            assert!(matches!(
+                result,
+                Err(DocumentVerificationError::Aadhaar(
+                    AadhaarVerificationError::UntrustedIssuer
+                ))
+            ));
+        } else {
+            assert!(matches!(
+                result,
+                Err(DocumentVerificationError::Aadhaar(
+                    AadhaarVerificationError::Disabled
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn pan_holder_name_skips_fathers_name() {
+        let stacked = "INCOME TAX DEPARTMENT\nFather's Name\nSURESH KUMAR\nName\nRahul Kumar\nDate of Birth\n15/08/1990";
+        assert_eq!(
+            pan_names(stacked),
+            (
+                Some("RAHUL KUMAR".to_string()),
+                Some("SURESH KUMAR".to_string())
+            )
+        );
+
+        let joined = "Name RAHUL KUMAR Father's Name SURESH KUMAR DOB 15/08/1990";
+        assert_eq!(
+            pan_names(joined),
+            (
+                Some("RAHUL KUMAR".to_string()),
+                Some("SURESH KUMAR".to_string())
+            )
+        );
+
+        assert_eq!(
+            pan_names("Name RAHUL KUMAR\n"),
+            (Some("RAHUL KUMAR".to_string()), None)
+        );
+    }
+
+    #[test]
+    fn gstin_matches_its_embedded_pan() {
+        assert_eq!(gstin_embedded_pan("27AAPFU0939F1ZV"), "AAPFU0939F");
+        assert!(gstin_matches_pan("27AAPFU0939F1ZV", "AAPFU0939F"));
+        assert!(gstin_matches_pan("29AAGCB7383J1Z4", "AAGCB7383J"));
+    }
+
+    #[test]
+    fn gstin_rejects_other_pan() {
+        assert!(!gstin_matches_pan("27AAPFU0939F1ZV", "AAGCB7383J"));
+        assert!(!gstin_matches_pan("27AAPFU0939F1ZV", "AAPFU0939"));
+        assert!(!gstin_matches_pan("27AAPFU", "AAPFU0939F"));
+    }
+
+    #[test]
+    fn same_entity_compares_embedded_pan_and_name_variants() {
+        use crate::{same_entity, EntityMatch, PANCertificate};
+
+        let gst = GSTCertificate {
+            legal_name: "ACME TRADERS PVT LTD".to_string(),
+            ..gst()
+        };
+        let pan = |pan_number: &str, legal_name: &str| PANCertificate {
+            pan_number: pan_number.to_string(),
+            legal_name: legal_name.to_string(),
+            fathers_name: None,
+            gender: None,
+            signature: gst.signature.clone(),
+            signer: SignerInfo::default(),
+            dob: "01/04/2015".to_string(),
+            print_date: None,
+            category: PanCategory::Firm,
+        };
+
+        let cases = [
+            (
+                "AAPFU0939F",
+                "Acme Traders Private Limited",
+                EntityMatch::Match,
+            ),
+            ("AAPFU0939F", "ACME TRADERS PVT LTD", EntityMatch::Match),
+            ("AAGCB7383J", "ACME TRADERS", EntityMatch::PanMismatch),
+            (
+                "AAPFU0939F",
+                "ACME INDUSTRIES LTD",
+                EntityMatch::NameMismatch,
+            ),
+            ("AAGCB7383J", "ACME INDUSTRIES LTD", EntityMatch::Both),
+        ];
+        for (pan_number, legal_name, expected) in cases {
+            assert_eq!(
+                same_entity(&gst, &pan(pan_number, legal_name)),
+                expected,
+                "{} / {}",
+                pan_number,
+                legal_name
+            );
+        }
+        assert!(same_entity(&gst, &pan("AAPFU0939F", "ACME TRADERS")).is_match());
+    }
+
+    /// Property tests of `extract_gstin` and `extract_pan` against the identifier rules
+    mod identifier_properties {
+        use super::*;
+        use crate::extract::{extract_gstin, extract_pan};
+        use proptest::prelude::*;
+
+        const PAN_CATEGORIES: &str = "PCHFABTLJG";
+
+        fn known_state_code() -> impl Strategy<Value = String> {
+            prop_oneof![1u8..=38, Just(97), Just(99)].prop_map(|code| format!("{:02}", code))
+        }
+
+        fn pan_with_category(
+            category: impl Strategy<Value = char>,
+        ) -> impl Strategy<Value = String> {
+            ("[A-Z]{3}", category, "[A-Z]", "[0-9]{4}", "[A-Z]").prop_map(
+                |(prefix, category, surname, digits, check)| {
+                    format!("{}{}{}{}{}", prefix, category, surname, digits, check)
+                },
+            )
+        }
+
+        fn valid_pan() -> impl Strategy<Value = String> {
+            pan_with_category(prop::sample::select(
+                PAN_CATEGORIES.chars().collect::<Vec<_>>(),
+            ))
+        }
+
+        /// First 14 characters of a GSTIN from its parts, with the check character appended
+        fn gstin_from(state_code: &str, pan: &str, entity: char) -> String {
+            let first14 = format!("{}{}{}Z", state_code, pan, entity);
+            let check = gstin_check_character(&first14).unwrap();
+            format!("{}{}", first14, check)
+        }
+
+        fn valid_gstin() -> impl Strategy<Value = String> {
+            (known_state_code(), "[A-Z]{5}[0-9]{4}[A-Z]", "[1-9A-Z]").prop_map(
+                |(state, pan, entity)| gstin_from(&state, &pan, entity.chars().next().unwrap()),
+            )
+        }
+
+        /// The GSTIN rules written out position by position, independent of the regex
+        fn is_valid_gstin(s: &str) -> bool {
+            let b = s.as_bytes();
+            b.len() == 15
+                && s[..2].parse().ok().and_then(gstin_state).is_some()
+                && b[2..7].iter().all(u8::is_ascii_uppercase)
+                && b[7..11].iter().all(u8::is_ascii_digit)
+                && b[11].is_ascii_uppercase()
+                && ((b'1'..=b'9').contains(&b[12]) || b[12].is_ascii_uppercase())
+                && b[13] == b'Z'
+                && gstin_check_character(&s[..14]) == Some(b[14] as char)
+        }
+
+        proptest! {
+            #[test]
+            fn accepts_valid_gstins_in_surrounding_text(
+                gstin in valid_gstin(),
+                before in "[a-z ]{0,20}",
+                after in "[a-z ]{0,20}",
+            ) {
+                let text = format!("{} {} {}", before, gstin, after);
+                prop_assert_eq!(extract_gstin(&text), Some(gstin));
+            }
+
+            #[test]
+            fn rejects_wrong_check_characters(gstin in valid_gstin(), check in "[0-9A-Z]") {
+                prop_assume!(!gstin.ends_with(check.as_str()));
+                let corrupted = format!("{}{}", &gstin[..14], check);
+                prop_assert_eq!(extract_gstin(&corrupted), None);
+            }
+
+            #[test]
+            fn accepts_exactly_the_valid_gstin_strings(
+                gstin in valid_gstin(),
+                position in 0usize..15,
+                replacement in "[0-9A-Z]",
+            ) {
+                let mut mutated = gstin.into_bytes();
+                mutated[position] = replacement.as_bytes()[0];
+                let mutated = String::from_utf8(mutated).unwrap();
+                prop_assert_eq!(
+                    extract_gstin(&mutated).is_some(),
+                    is_valid_gstin(&mutated),
+                    "{}",
+                    mutated
+                );
+            }
+
+            #[test]
+            fn rejects_state_code_zero(pan in "[A-Z]{5}[0-9]{4}[A-Z]", entity in "[1-9A-Z]") {
+                let gstin = gstin_from("00", &pan, entity.chars().next().unwrap());
+                prop_assert_eq!(extract_gstin(&gstin), None);
+            }
+
+            #[test]
+            fn rejects_entity_code_zero(state in known_state_code(), pan in "[A-Z]{5}[0-9]{4}[A-Z]") {
+                let gstin = gstin_from(&state, &pan, '0');
+                prop_assert_eq!(extract_gstin(&gstin), None);
+            }
+
+            #[test]
+            fn accepts_pans_with_known_categories(pan in valid_pan(), before in "[a-z ]{0,20}") {
+                let text = format!("{} {}", before, pan);
+                prop_assert_eq!(extract_pan(&text), Some(pan));
+            }
+
+            #[test]
+            fn rejects_pans_with_unknown_categories(
+                pan in pan_with_category(
+                    prop::char::range('A', 'Z').prop_filter("known category", |c| {
+                        !PAN_CATEGORIES.contains(*c)
+                    }),
+                ),
+            ) {
+                prop_assert_eq!(extract_pan(&pan), None);
+            }
+        }
+    }
+
+    /// Every committed PAN field, edited alone, must change `pan_generate_commitment`
+    mod pan_commitment_matrix {
+        use super::*;
+        use crate::PANCertificate;
+
+        const SALT: [u8; 32] = [7u8; 32];
+
+        /// A field name and an edit of only that field
+        type FieldEdit = (&'static str, fn(&mut PANCertificate));
+
+        fn pan() -> PANCertificate {
+            PANCertificate {
+                pan_number: "ABCPE1234F".to_string(),
+                legal_name: "RAHUL KUMAR".to_string(),
+                fathers_name: Some("SURESH KUMAR".to_string()),
+                gender: Some(Gender::Male),
+                signature: PdfSignatureResult {
+                    is_valid: true,
+                    message_digest: vec![1; 32],
+                    public_key: vec![2; 64],
+                },
+                signer: SignerInfo::default(),
+                dob: "15/08/1990".to_string(),
+                print_date: None,
+                category: PanCategory::Individual,
+            }
+        }
+
+        fn commitment(cert: &PANCertificate) -> [u8; 32] {
+            pan_generate_commitment(cert, SALT).unwrap()
+        }
+
+        #[test]
+        fn identical_input_commits_identically() {
+            assert_eq!(commitment(&pan()), commitment(&pan()));
+            assert_eq!(
+                pan_generate_commitment_masked(&pan(), &DisclosureMask::REVEAL_ALL, SALT).unwrap(),
+                commitment(&pan())
+            );
+        }
+
+        #[test]
+        fn parses_of_one_document_deduplicate() {
+            use std::collections::HashSet;
+
+            let mut reparsed = pan();
+            reparsed.print_date = NaiveDate::from_ymd_opt(2020, 1, 1);
+            reparsed.signer.issuer = Some("NSDL e-Gov".to_string());
+            let mut other = pan();
+            other.pan_number = "ABCPE1234G".to_string();
+
+            let certificates: HashSet<PANCertificate> = [pan(), reparsed, other].into();
+            assert_eq!(certificates.len(), 2);
+        }
+
+        #[test]
+        fn uncommittable_certificates_compare_by_number_and_digest() {
+            let unsigned = |pan_number: &str| {
+                let mut cert = pan();
+                cert.pan_number = pan_number.to_string();
+                cert.signature.is_valid = false;
+                cert
+            };
+            assert_eq!(unsigned("ABCPE1234F"), unsigned("ABCPE1234F"));
+            assert_ne!(unsigned("ABCPE1234F"), unsigned("ABCPE1234G"));
+            assert_ne!(unsigned("ABCPE1234F"), pan());
+
+            let mut resigned = unsigned("ABCPE1234F");
+            resigned.signature.message_digest[0] ^= 1;
+            assert_ne!(unsigned("ABCPE1234F"), resigned);
+        }
+
+        #[test]
+        fn preimage_hashes_to_the_commitment() {
+            let preimage = pan_commitment_preimage(&pan(), SALT).unwrap();
+            assert_eq!(keccak256(&preimage).0, commitment(&pan()));
+        }
+
+        #[test]
+        fn editing_any_committed_field_changes_the_commitment() {
+            let edits: [FieldEdit; 7] = [
+                ("pan", |cert| cert.pan_number = "ABCPE1234G".to_string()),
+                ("name", |cert| cert.legal_name = "RAHUL KUMAR S".to_string()),
+                ("dob", |cert| cert.dob = "16/08/1990".to_string()),
+                ("key", |cert| cert.signature.public_key[63] ^= 1),
+                ("digest", |cert| cert.signature.message_digest[0] ^= 1),
+                ("gender", |cert| cert.gender = Some(Gender::Female)),
+                ("no gender", |cert| cert.gender = None),
+            ];
+
+            let original = commitment(&pan());
+            for (field, edit) in edits {
+                let mut cert = pan();
+                edit(&mut cert);
+                assert_ne!(commitment(&cert), original, "{} is not committed", field);
+            }
+            assert_ne!(
+                pan_generate_commitment(&pan(), [8u8; 32]).unwrap(),
+                original
+            );
+        }
+
+        #[test]
+        fn moving_bytes_between_fields_changes_the_commitment() {
+            let mut cert = pan();
+            cert.legal_name = "RAHUL KUMAR1".to_string();
+            cert.dob = "5/08/1990".to_string();
+            assert_ne!(commitment(&cert), commitment(&pan()));
+        }
+
+        #[test]
+        fn fields_outside_the_commitment_do_not_change_it() {
+            let mut cert = pan();
+            cert.fathers_name = None;
+            cert.print_date = NaiveDate::from_ymd_opt(2020, 1, 1);
+            cert.signer.issuer = Some("NSDL".to_string());
+            assert_eq!(commitment(&cert), commitment(&pan()));
+        }
+    }
+
+    /// `issuers::verify_signer_chain_with` against throwaway 1024-bit test CAs
+    #[cfg(feature = "embedded-issuers")]
+    mod signer_chain {
+        use super::*;
+        use crate::issuers::{
+            pem_to_der, verify_signer_chain, verify_signer_chain_with, ChainError,
+            PINNED_ISSUER_PEMS,
+        };
+
+        /// Self-signed "Test Issuer CA"
+        const TEST_CA_PEM: &[u8] = b"-----BEGIN CERTIFICATE-----\n\
+MIICEDCCAXmgAwIBAgIUeWgRr5sXac4yTwwELh5GBSAv11EwDQYJKoZIhvcNAQEL\n\
+BQAwGTEXMBUGA1UEAwwOVGVzdCBJc3N1ZXIgQ0EwIBcNMjYxMDE3MDMxNDM3WhgP\n\
+MjEyNjA5MjMwMzE0MzdaMBkxFzAVBgNVBAMMDlRlc3QgSXNzdWVyIENBMIGfMA0G\n\
+CSqGSIb3DQEBAQUAA4GNADCBiQKBgQDncNbm2jBzEhSmKmk4zpd5PDFvW7NsyPJy\n\
+4m6bIcLtkucbFfvBJaFKvgt0xY0o/dXWkAZwVf+exAi+MWUUj1HCG7spCK2k3AmU\n\
+Ez1DN+4eCF/10AQsC9jPnq0rHx3wID0zcXIY8uL6WMaQS2oneYrrGGCKVGLpQq5H\n\
+RZ8RR9FtYQIDAQABo1MwUTAdBgNVHQ4EFgQUoQh9mkklwLucVOhnuFOZ//yPzQMw\n\
+HwYDVR0jBBgwFoAUoQh9mkklwLucVOhnuFOZ//yPzQMwDwYDVR0TAQH/BAUwAwEB\n\
+/zANBgkqhkiG9w0BAQsFAAOBgQAGo9uZg4KW5L4cUt7aAY7CzvwTNGz52ZZF+h7A\n\
+oK1eyYhiwtfah9Pv5ODe/DUvWrwIcaiZRCreU+Dd0BLxG3xlVVNObAaPrXcw7bGg\n\
+8wy8FMs6dRmT9ehtsV4bIKRNlutCAOCUYRc9B4XgtE8q5IcEDl1W34SjP6ZbbbSj\n\
+O2kMVQ==\n\
+-----END CERTIFICATE-----";
+
+        /// Another self-signed CA with the same subject but its own key
+        const ROGUE_CA_PEM: &[u8] = b"-----BEGIN CERTIFICATE-----\n\
+MIICEDCCAXmgAwIBAgIUTwvvG+qG8ngEWwCH05yPKD8B5yAwDQYJKoZIhvcNAQEL\n\
+BQAwGTEXMBUGA1UEAwwOVGVzdCBJc3N1ZXIgQ0EwIBcNMjYxMDE3MDMxNDM3WhgP\n\
+MjEyNjA5MjMwMzE0MzdaMBkxFzAVBgNVBAMMDlRlc3QgSXNzdWVyIENBMIGfMA0G\n\
+CSqGSIb3DQEBAQUAA4GNADCBiQKBgQDp7iP7rqfB12VNAQImfvnuVMA7hmPTvlbc\n\
+60TZK8SH2ryN+uctEk5s6SrVU2a/BVxSczoie+WZGqb1nT4ZjsNgNM86P6EH3V27\n\
+qG86GuAqsLbUa1cEKbiw/yvyWIG3vO8s3Ki+n5kPsR7Pchm0urId0B/kEPyz5s2H\n\
+OYYvp/gbywIDAQABo1MwUTAdBgNVHQ4EFgQUv+cPWNR5gU8DQ5Z5OfR5GExcCTsw\n\
+HwYDVR0jBBgwFoAUv+cPWNR5gU8DQ5Z5OfR5GExcCTswDwYDVR0TAQH/BAUwAwEB\n\
+/zANBgkqhkiG9w0BAQsFAAOBgQDXomMPkdrAHxURIowwWZr7zGN/zDbsAayv2lit\n\
+uaySX3Qfr+ct5e6jnHkx9ht4i6PTYi/O0BaEBH1++RCYcDNWxY9zqY7E0fXkMi49\n\
+hJN0IqaiZPeWCBhIjLV2ielbYrpfXS9190j+iGIeiO3R6gM7XB5oEuHH2uvVq9ba\n\
+616pLg==\n\
+-----END CERTIFICATE-----";
+
+        /// "Test Signer", issued by `TEST_CA_PEM`
+        const SIGNER_PEM: &[u8] = b"-----BEGIN CERTIFICATE-----\n\
+MIIB/DCCAWWgAwIBAgIUCmHUpeMfB1LMMHMVbM+t5JYMkBkwDQYJKoZIhvcNAQEL\n\
+BQAwGTEXMBUGA1UEAwwOVGVzdCBJc3N1ZXIgQ0EwIBcNMjYxMDE3MDMxNDM3WhgP\n\
+MjEyNjA5MjMwMzE0MzdaMBYxFDASBgNVBAMMC1Rlc3QgU2lnbmVyMIGfMA0GCSqG\n\
+SIb3DQEBAQUAA4GNADCBiQKBgQDRErE8mawEjHI9rkJmEIV1po1QBf4fkLAzx+GA\n\
+B9osNxWaJAeyFQVF3YffEeYLcsAb+0lFvsIKx3P7VE0GaesWcVTluMHEc7yI7gVC\n\
+KRGyqhx7+3LteuuiAs9UItMhQo0ju7bW95X8Pw5wBH4nEfQ1sgwwmveCz1yyElJa\n\
+pwK47QIDAQABo0IwQDAdBgNVHQ4EFgQUZvxUPAIaH9EsCBGa246ykHmEGM8wHwYD\n\
+VR0jBBgwFoAUoQh9mkklwLucVOhnuFOZ//yPzQMwDQYJKoZIhvcNAQELBQADgYEA\n\
+5ZME7G2WVjyaW9uZv+J2u6rnePHd09hhLsEgQHe2xsZZ4R2AImXrrat2z1Gf2Q2l\n\
+P4lvZnd8T5dg1jkca5UAjx7GRclLfI+gUGyrw3JcnSFwoUp/VN3OA8NWc5w3V4TY\n\
+8pi2EqTA+NjtOqgrE800Cu+f0WeQwRsWAZyscvdIw8Q=\n\
+-----END CERTIFICATE-----";
+
+        #[test]
+        fn signer_chains_only_to_the_ca_that_signed_it() {
+            let signer = pem_to_der(SIGNER_PEM).unwrap();
+
+            assert_eq!(verify_signer_chain_with(&signer, &[TEST_CA_PEM]), Ok(()));
+            assert_eq!(
+                verify_signer_chain_with(&signer, &[ROGUE_CA_PEM, TEST_CA_PEM]),
+                Ok(())
+            );
+            assert_eq!(
+                verify_signer_chain_with(&signer, &[ROGUE_CA_PEM]),
+                Err(ChainError::SignatureMismatch)
+            );
+            assert_eq!(
+                verify_signer_chain_with(&signer, &[]),
+                Err(ChainError::NoPinnedIssuer)
+            );
+            assert_eq!(
+                verify_signer_chain(&signer),
+                Err(ChainError::NoPinnedIssuer)
+            );
+        }
+
+        /// "Test Signer", signed with its own key but naming "Test Issuer CA" as issuer
+        const FORGED_SIGNER_PEM: &[u8] = b"-----BEGIN CERTIFICATE-----\n\
+MIIByDCCATGgAwIBAgIBBzANBgkqhkiG9w0BAQsFADAZMRcwFQYDVQQDDA5UZXN0\n\
+IElzc3VlciBDQTAgFw0yNjEwMTcwNDIxMjlaGA8yMTI2MDkyMzA0MjEyOVowFjEU\n\
+MBIGA1UEAwwLVGVzdCBTaWduZXIwgZ8wDQYJKoZIhvcNAQEBBQADgY0AMIGJAoGB\n\
+ALKso8XzJH11lLhyArA8ED45Htm/4E6PM70qeW3bK7OShG0Ue3n1NNUBwyXEFavl\n\
+Af82WdFs/1MueKFRgFGKI0YkDpYhr1AD+HgCvTMdGxa21GUNXyEdZzFVleCLKUi6\n\
+9kXvidAGxZhqrpGDaJ+jAa9pVdP4B+hSMeHMVF5ul8jrAgMBAAGjITAfMB0GA1Ud\n\
+DgQWBBTnH+qkxiU2jDvi5H9TaWX8Ot76gDANBgkqhkiG9w0BAQsFAAOBgQBcNbsW\n\
+pDNC375gJIA3pV9nw8oOIeQVl37r2c8P/qHplJ2TSoChbd6vUIXci9JLEFY1Hwtf\n\
+t5oEZhYmKMUQz4BczVudnXz9KeVa6xFmZ6SZ2ZH2aoYTIpJB+YMyupdK03gwMFMK\n\
+8mmhRNVOAqJjZBhjSy4hDTl9YEKHwtrOoS1BaQ==\n\
+-----END CERTIFICATE-----";
+
+        #[test]
+        fn self_signed_is_decided_by_key_not_name() {
+            assert!(signer_is_self_signed(&pem_to_der(TEST_CA_PEM).unwrap()));
+            assert!(!signer_is_self_signed(&pem_to_der(SIGNER_PEM).unwrap()));
+            assert!(signer_is_self_signed(
+                &pem_to_der(FORGED_SIGNER_PEM).unwrap()
+            ));
+        }
+
+        #[test]
+        fn malformed_pins_are_skipped() {
+            let signer = pem_to_der(SIGNER_PEM).unwrap();
+            let truncated = &TEST_CA_PEM[..TEST_CA_PEM.len() / 2];
+
+            assert_eq!(
+                verify_signer_chain_with(&signer, &[b"not a certificate", truncated, TEST_CA_PEM]),
+                Ok(())
+            );
+            assert_eq!(
+                verify_signer_chain_with(&signer, &[truncated]),
+                Err(ChainError::NoPinnedIssuer)
+            );
+        }
+
+        /// Skipping bad pins must not hide a broken one shipped in the binary
+        #[test]
+        fn every_pinned_issuer_parses() {
+            for pem in PINNED_ISSUER_PEMS {
+                let der = pem_to_der(pem).expect("pinned issuer is not PEM");
+                // Only an unparseable certificate fails before the issuer lookup
+                assert_eq!(
+                    verify_signer_chain_with(&der, &[]),
+                    Err(ChainError::NoPinnedIssuer)
+                );
+            }
+        }
+
+        #[test]
+        fn edited_or_bare_signer_keys_do_not_chain() {
+            let mut der = pem_to_der(SIGNER_PEM).unwrap();
+            let name = der
+                .windows(b"Test Signer".len())
+                .position(|window| window == b"Test Signer")
+                .unwrap();
+            der[name] = b'B';
+            assert_eq!(
+                verify_signer_chain_with(&der, &[TEST_CA_PEM]),
+                Err(ChainError::SignatureMismatch)
+            );
+
+            assert_eq!(
+                verify_signer_chain_with(&[2; 64], &[TEST_CA_PEM]),
+                Err(ChainError::MalformedCertificate)
+            );
+        }
+
+        #[test]
+        fn gst_sample_signer_certificate_is_checked_against_the_pinned_issuers() {
+            let certificates =
+                embedded_certificates(include_bytes!("../../samples/GST-certificate.pdf"));
+
+            // Parsed and looked up by issuer name, which no test CA has
+            assert_eq!(
+                verify_signer_chain_with(&certificates[0], &[TEST_CA_PEM]),
+                Err(ChainError::NoPinnedIssuer)
+            );
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Verifies the signed sample PDFs in `samples/` end to end, so the extraction patterns are
+//! checked against real certificate layouts rather than hand-written text.
+//!
+//! Off-chain only; the zkVM build reads PDFs from stdin instead of the file system.
+//! ```shell
+//! cargo test -p zkpdf-template-lib --test samples
+//! ```
+#![cfg(not(feature = "zkvm"))]
+
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use zkpdf_template_lib::{
+    utils::{pan_holder_category, validate_gstin_checksum},
+    verify_certificate, verify_gst_certificate, verify_pan_certificate, CertificateOutcome,
+};
+
+const SAMPLE_GST_PDF: &[u8] = include_bytes!("../../samples/GST-certificate.pdf");
+
+/// A bundled sample, or `None` when it is not checked in
+fn sample(name: &str) -> Option<Vec<u8>> {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", "samples", name]
+        .iter()
+        .collect();
+    std::fs::read(path).ok()
+}
+
+#[test]
+fn gst_sample_verifies_with_its_registration_number() {
+    let cert = verify_gst_certificate(SAMPLE_GST_PDF.to_vec()).expect("GST sample failed");
+
+    assert!(cert.signature.is_valid);
+    assert_eq!(cert.gst_number, "07AAATC0869P1ZB");
+    assert!(validate_gstin_checksum(&cert.gst_number));
+    assert_eq!(cert.legal_name, "CONSUMER UNITY AND TRUST SOCIETY");
+    assert_eq!(cert.state_code, 7);
+    assert_eq!(cert.state_name.as_deref(), Some("Delhi"));
+
+    // zkpdf returns only the signer's key; the certificate is read from the signature
+    assert_eq!(
+        cert.signer.issuer.as_deref(),
+        Some("DS GOODS AND SERVICES TAX NETWORK 07")
+    );
+    assert_eq!(
+        cert.signer.validity,
+        Some((
+            NaiveDate::from_ymd_opt(2022, 9, 30).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 9, 30).unwrap()
+        ))
+    );
+
+    // Annexure A lists no additional places; Annexure B one committee member
+    let annexure = cert.annexure.expect("GST sample has annexure pages");
+    assert!(annexure.additional_places.is_empty());
+    assert_eq!(annexure.partners, vec!["VIJAY SINGH"]);
+}
+
+#[test]
+#[cfg(feature = "embedded-issuers")]
+#[ignore = "the Capricorn Sub CA for Document Signer DSC 2022 is not pinned in issuers yet"]
+fn gst_sample_chains_to_its_pinned_issuer() {
+    let cert = verify_gst_certificate(SAMPLE_GST_PDF.to_vec()).expect("GST sample failed");
+
+    assert!(cert.signer.chain_valid);
+}
+
+#[test]
+fn gst_sample_verifies_as_a_gst_certificate() {
+    let outcome = verify_certificate(SAMPLE_GST_PDF.to_vec()).expect("GST sample failed");
+
+    let CertificateOutcome::Gst(cert) = outcome else {
+        panic!("GST sample was not detected as a GST certificate");
+    };
+    assert_eq!(cert.gst_number, "07AAATC0869P1ZB");
+}
+
+#[test]
+fn gst_sample_verifies_to_one_certificate_identity() {
+    let first = verify_gst_certificate(SAMPLE_GST_PDF.to_vec()).expect("GST sample failed");
+    let second = verify_gst_certificate(SAMPLE_GST_PDF.to_vec()).expect("GST sample failed");
+
+    let certificates: HashSet<_> = [first, second].into_iter().collect();
+    assert_eq!(certificates.len(), 1);
+}
+
+#[test]
+#[ignore = "no PAN sample bundled"]
+fn pan_sample_verifies_when_bundled() {
+    // The script defaults to samples/PAN-card.pdf, but no signed e-PAN is checked in yet
+    let pdf_bytes = sample("PAN-card.pdf").expect("samples/PAN-card.pdf is not bundled");
+    let cert = verify_pan_certificate(pdf_bytes.clone()).expect("PAN sample failed");
+    assert!(matches!(
+        verify_certificate(pdf_bytes),
+        Ok(CertificateOutcome::Pan(detected)) if detected.pan_number == cert.pan_number
+    ));
+
+    assert!(cert.signature.is_valid);
+    assert_eq!(pan_holder_category(&cert.pan_number), Some(cert.category));
+    assert!(!cert.legal_name.is_empty());
+}
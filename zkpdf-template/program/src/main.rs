@@ -1,14 +1,21 @@
 //! GST Certificate Verification Program
 //!
-//! This program verifies GST certificate PDFs and extracts key information:
-//! - GST number
-//! - Legal name  
+//! This program verifies GST certificate, PAN, Driving License and e-Aadhaar PDFs and extracts
+//! key information:
+//! - GST, PAN or DL number, or the last four digits of the Aadhaar number
+//! - Legal name
 //! - Digital signature validity
-//! - Document commitment hash
+//! - Field commitment hash, over the extracted fields
+//! - Document commitment hash, over the full page text
 //! - Public key hash
 //!
 //! The program runs inside the SP1 zkVM to generate zero-knowledge proofs
 //! that prove the document is valid without revealing sensitive data.
+//!
+//! Building with the `profile` feature emits SP1 cycle-tracker markers around the
+//! `pdf-verification`, `field-extraction` and `commitment` phases. Run the script with
+//! `RUST_LOG=info cargo run --release --features profile -- --execute --kind 0` and the
+//! executor logs the cycles spent inside each phase when it reaches its end marker.
 
 // These two lines are necessary for the program to properly compile.
 //
@@ -17,61 +24,154 @@
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
-use alloy_primitives::keccak256;
-use alloy_sol_types::SolType;
-use zkpdf_template_lib::{utils::{gst_generate_commitment, pan_generate_commitment}, verify_gst_certificate, verify_pan_certificate, GSTValuesStruct, PANValuesStruct};
+use zkpdf_template_lib::{
+    commit_aadhaar, commit_bundle, commit_dl, commit_gst, commit_pan,
+    utils::{
+        commitments_equal, parse_indian_dob, prove_age_threshold, DisclosureMask, MAX_PDF_BYTES,
+    },
+    verify_any_document, CertificateOutcome, CommitContext, VerifiedDocument,
+};
+
+/// Asserts the document commitment equals the expected one when the caller supplied it,
+/// returning whether the check was made
+fn check_expected_commitment(document_commitment: &[u8; 32], expected: Option<[u8; 32]>) -> bool {
+    match expected {
+        Some(expected) => {
+            assert!(
+                commitments_equal(document_commitment, &expected),
+                "Document commitment does not match the expected commitment"
+            );
+            true
+        }
+        None => false,
+    }
+}
 
 pub fn main() {
-    // Read PDF bytes from the prover
-    let pdf_bytes = sp1_zkvm::io::read::<Vec<u8>>();
-
-    // GST
-    if let Ok(gst_cert) = verify_gst_certificate(pdf_bytes.clone()) {
-        let document_commitment = gst_generate_commitment(&gst_cert);
-        let public_key_hash = keccak256(&gst_cert.signature.public_key);
-
-        let gst_bytes = GSTValuesStruct::abi_encode(&GSTValuesStruct {
-            gst_number: gst_cert.gst_number,
-            legal_name: gst_cert.legal_name,
-            signature_valid: gst_cert.signature.is_valid,
-            document_commitment: document_commitment
-                .as_slice()
-                .try_into()
-                .expect("Failed to convert document commitment to FixedBytes"),
-            public_key_hash: public_key_hash
-                .as_slice()
-                .try_into()
-                .expect("Failed to convert public key hash to FixedBytes"),
-        });
-
-        sp1_zkvm::io::commit_slice(&gst_bytes);
-        return; // Stop here since GST certificate was found
+    // Read the PDFs from the prover; more than one is proven as a bundle
+    let pdfs = sp1_zkvm::io::read::<Vec<Vec<u8>>>();
+    assert!(!pdfs.is_empty(), "No PDF supplied");
+
+    // The limit is fixed in the circuit so every proof bounds parsing the same way
+    for pdf_bytes in &pdfs {
+        assert!(
+            pdf_bytes.len() <= MAX_PDF_BYTES,
+            "PDF is {} bytes, above the {} byte limit",
+            pdf_bytes.len(),
+            MAX_PDF_BYTES
+        );
     }
 
-    // PAN
-    if let Ok(pan_cert) = verify_pan_certificate(pdf_bytes.clone()) {
-        let document_commitment = pan_generate_commitment(&pan_cert);
-        let public_key_hash = keccak256(&pan_cert.signature.public_key);
-
-        let pan_bytes = PANValuesStruct::abi_encode(&PANValuesStruct {
-            pan_number: pan_cert.pan_number,
-            legal_name: pan_cert.legal_name,
-            signature_valid: pan_cert.signature.is_valid,
-            document_commitment: document_commitment
-                .as_slice()
-                .try_into()
-                .expect("Failed to convert document commitment to FixedBytes"),
-            public_key_hash: public_key_hash
-                .as_slice()
-                .try_into()
-                .expect("Failed to convert public key hash to FixedBytes"),
-            dob: pan_cert.dob,
-        });
-
-        sp1_zkvm::io::commit_slice(&pan_bytes);
-        return; // Stop here since PAN certificate was found
+    // Read which GST fields and whether the PAN holder's gender are revealed in the public values
+    let disclosure = DisclosureMask {
+        gst_number: sp1_zkvm::io::read::<bool>(),
+        legal_name: sp1_zkvm::io::read::<bool>(),
+        address: sp1_zkvm::io::read::<bool>(),
+        gender: sp1_zkvm::io::read::<bool>(),
+    };
+
+    // Read the salt mixed into the document commitment
+    let salt = sp1_zkvm::io::read::<[u8; 32]>();
+
+    // Read the PAN age thresholds to prove and the date they are evaluated on
+    let age_thresholds = sp1_zkvm::io::read::<Vec<u32>>();
+    let age_as_of = sp1_zkvm::io::read::<String>();
+
+    // Read the document commitment the caller expects the PDF to hash to, if any
+    let expected_commitment = sp1_zkvm::io::read::<Option<[u8; 32]>>();
+
+    // Read whether to commit the signer's full public key rather than only its hash
+    let include_public_key = sp1_zkvm::io::read::<bool>();
+
+    // Read whether GST values are committed in the fixed-size packed layout
+    let packed = sp1_zkvm::io::read::<bool>();
+
+    // Read the unix time of proving. Nothing inside the zkVM can check it, so it is only
+    // as trustworthy as the prover that supplied it
+    let proof_time = sp1_zkvm::io::read::<u64>();
+
+    // Several PDFs commit one bundle of per-document commitments
+    if pdfs.len() > 1 {
+        assert!(
+            expected_commitment.is_none(),
+            "An expected commitment can only be checked for a single PDF"
+        );
+        assert!(
+            !include_public_key,
+            "The full public key can only be committed for a single PDF"
+        );
+        assert!(!packed, "Bundles have no packed public values layout");
+        let documents: Vec<VerifiedDocument> = pdfs
+            .into_iter()
+            .map(|pdf_bytes| {
+                verify_any_document(pdf_bytes).unwrap_or_else(|err| {
+                    panic!(
+                        "Bundled PDF is not a valid GST, PAN, DL or Aadhaar certificate: {}",
+                        err
+                    )
+                })
+            })
+            .collect();
+        commit_bundle(&documents, &disclosure, salt, proof_time);
+        return;
     }
+    let pdf_bytes = pdfs.into_iter().next().unwrap();
+
+    // Fail the program if no supported document was found
+    let VerifiedDocument {
+        certificate,
+        document_commitment,
+        ..
+    } = verify_any_document(pdf_bytes).unwrap_or_else(|err| {
+        panic!(
+            "No valid GST, PAN, DL or Aadhaar certificate found in PDF: {}",
+            err
+        )
+    });
+
+    let ctx = CommitContext {
+        disclosure,
+        salt,
+        document_commitment,
+        committed_match: check_expected_commitment(&document_commitment, expected_commitment),
+        include_public_key,
+        packed,
+        proven_at: proof_time,
+    };
+
+    // The lib encodes the struct matching each document kind, so the layout cannot drift
+    match certificate {
+        // GST, packed if asked for, else as the private struct unless every field is revealed
+        CertificateOutcome::Gst(gst_cert) => commit_gst(&gst_cert, &ctx),
 
-    // If neither GST nor PAN was found, fail the program
-    panic!("No valid GST or PAN certificate found in PDF");
+        // PAN
+        CertificateOutcome::Pan(pan_cert) => {
+            let age_over_thresholds = if age_thresholds.is_empty() {
+                Vec::new()
+            } else {
+                let dob = parse_indian_dob(&pan_cert.dob)
+                    .unwrap_or_else(|err| panic!("Failed to parse DOB: {}", err));
+                let as_of = parse_indian_dob(&age_as_of)
+                    .unwrap_or_else(|err| panic!("Failed to parse age as-of date: {}", err));
+                age_thresholds
+                    .iter()
+                    .map(|threshold| prove_age_threshold(&dob, *threshold, as_of))
+                    .collect()
+            };
+
+            commit_pan(
+                &pan_cert,
+                &ctx,
+                age_thresholds,
+                age_over_thresholds,
+                age_as_of,
+            );
+        }
+
+        // Driving License
+        CertificateOutcome::Dl(dl_cert) => commit_dl(&dl_cert, &ctx),
+
+        // Aadhaar, of which only the last four digits are ever read
+        CertificateOutcome::Aadhaar(aadhaar_cert) => commit_aadhaar(&aadhaar_cert, &ctx),
+    }
 }
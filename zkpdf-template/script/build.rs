@@ -1,5 +1,14 @@
-use sp1_build::build_program_with_args;
+use sp1_build::{build_program_with_args, BuildArgs};
 
 fn main() {
-    build_program_with_args("../program", Default::default())
+    let mut args = BuildArgs::default();
+    // Forward `--features profile` to the zkVM program
+    if std::env::var_os("CARGO_FEATURE_PROFILE").is_some() {
+        args.features.push("profile".to_string());
+    }
+    // Forward `--features aadhaar` to the zkVM program
+    if std::env::var_os("CARGO_FEATURE_AADHAAR").is_some() {
+        args.features.push("aadhaar".to_string());
+    }
+    build_program_with_args("../program", args)
 }
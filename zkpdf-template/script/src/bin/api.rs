@@ -8,6 +8,7 @@ use sp1_sdk::{include_elf, ProverClient, SP1ProofWithPublicValues, SP1Stdin};
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
 use tower_http::cors::{Any, CorsLayer};
+use zkpdf_template_lib::utils::max_pdf_bytes_from_env;
 
 pub const ZKPDF_ELF: &[u8] = include_elf!("zkpdf-template-program");
 
@@ -28,8 +29,35 @@ async fn prove(Json(body): Json<ProofRequest>) -> Result<Json<SP1ProofWithPublic
 
     let ProofRequest { pdf_bytes } = body;
 
+    let limit = max_pdf_bytes_from_env();
+    if pdf_bytes.len() > limit {
+        return Err(format!(
+            "PDF is {} bytes, above the {} byte limit",
+            pdf_bytes.len(),
+            limit
+        ));
+    }
+
     let mut stdin = SP1Stdin::new();
-    stdin.write(&pdf_bytes);
+    stdin.write(&vec![pdf_bytes]);
+    // Reveal every GST field and the PAN holder's gender
+    stdin.write(&true);
+    stdin.write(&true);
+    stdin.write(&true);
+    stdin.write(&true);
+    // Zero commitment salt
+    stdin.write(&[0u8; 32]);
+    // No PAN age thresholds
+    stdin.write(&Vec::<u32>::new());
+    stdin.write(&chrono::Utc::now().format("%Y-%m-%d").to_string());
+    // No expected document commitment
+    stdin.write(&None::<[u8; 32]>);
+    // Commit only the hash of the signer's public key
+    stdin.write(&false);
+    // Commit the verbose GST layout
+    stdin.write(&false);
+    // Proving time, attested by this prover only
+    stdin.write(&(chrono::Utc::now().timestamp().max(0) as u64));
 
     let proof = client
         .prove(&pk, &stdin)
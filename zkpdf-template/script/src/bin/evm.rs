@@ -13,17 +13,27 @@
 //! ```shell
 //! RUST_LOG=info cargo run --release --bin evm -- --system groth16 --pdf-path path/to/your/certificate.pdf
 //! ```
+//! To check that an existing GST fixture's fields agree with its public values, without
+//! proving anything:
+//! ```shell
+//! cargo run --release --bin evm -- --check-fixture ../contracts/src/fixtures/groth16-fixture.json
+//! ```
 
-use alloy_sol_types::SolType;
 use alloy_primitives::keccak256;
-use chrono::{NaiveDate, Utc};
+use chrono::Utc;
 use clap::{Parser, ValueEnum};
 use serde::{Deserialize, Serialize};
 use sp1_sdk::{
     include_elf, HashableKey, ProverClient, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey,
 };
+use std::fmt;
 use std::path::PathBuf;
-use zkpdf_template_lib::{GSTValuesStruct, PANValuesStruct};
+use zkpdf_template_lib::{
+    decode_public_values,
+    hex_util::{from_0x, to_0x},
+    utils::{commitments_equal, max_pdf_bytes_from_env, PublicValuesError},
+    DecodedDocument, GSTValuesStruct, PANValuesStruct,
+};
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const ZKPDF_TEMPLATE_ELF: &[u8] = include_elf!("zkpdf-template-program");
@@ -36,6 +46,9 @@ struct EVMArgs {
     system: ProofSystem,
     #[arg(long, default_value = "../samples/PAN-card.pdf")]
     pdf_path: String,
+    /// Validate an existing GST fixture JSON instead of generating a proof
+    #[arg(long)]
+    check_fixture: Option<PathBuf>,
 }
 
 /// Enum representing the available proof systems
@@ -51,9 +64,13 @@ enum ProofSystem {
 struct SP1GSTProofFixture {
     gst_number: String,
     legal_name: String,
+    trade_name: String,
+    registration_timestamp: u64,
+    registration_type: String,
     signature_valid: bool,
     document_commitment: String,
     public_key_hash: String,
+    proven_at: u64,
     vkey: String,
     public_values: String,
     proof: String,
@@ -67,6 +84,8 @@ struct SP1PANProofFixture {
     holder_name: String,             // Legal name on PAN
     dob_commitment: String,          // Commitment to Date of Birth
     age_proof_over18: bool,          // ZK check: is age >= 18
+    print_timestamp: u64,            // e-PAN issue/print date, 0 when not printed
+    proven_at: u64,                  // Prover-attested proving time
     signature_valid: bool,           // Whether the digital signature on the PAN PDF is valid
     document_commitment: String,     // Commitment to the full PAN PDF
     public_key_hash: String,         // Hash of the issuer's signing key
@@ -93,6 +112,91 @@ struct SP1DLProofFixture {
     proof: String,                    // Proof bytes
 }
 
+/// Why a fixture's fields disagree with its own public values.
+#[derive(Debug)]
+enum FixtureError {
+    InvalidHex(&'static str),
+    PublicValues(PublicValuesError),
+    NotGst,
+    FieldMismatch(&'static str),
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FixtureError::InvalidHex(field) => write!(f, "{} is not valid hex", field),
+            FixtureError::PublicValues(err) => write!(f, "public values do not decode: {}", err),
+            FixtureError::NotGst => write!(f, "public values are not fully revealed GST values"),
+            FixtureError::FieldMismatch(field) => {
+                write!(f, "{} does not match the public values", field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FixtureError {}
+
+/// Checks that every field of a GST fixture equals the value decoded from its
+/// `public_values`, catching fixtures edited by hand or written by a stale script.
+///
+/// Only the fields' consistency is checked. The commitments themselves cannot be
+/// recomputed here: they are salted and bind the signed digest and page text, none of
+/// which are public. Neither is the proof checked.
+fn check_gst_fixture_fields(fixture: &SP1GSTProofFixture) -> Result<(), FixtureError> {
+    let decode_hex = |field: &'static str, value: &str| {
+        from_0x(value).map_err(|_| FixtureError::InvalidHex(field))
+    };
+    let hash_matches = |field: &'static str, value: &str, expected: &[u8; 32]| {
+        let hash = decode_hex(field, value)?;
+        Ok::<_, FixtureError>(
+            <&[u8; 32]>::try_from(hash.as_slice())
+                .is_ok_and(|hash| commitments_equal(hash, expected)),
+        )
+    };
+    let public_values = decode_hex("publicValues", &fixture.public_values)?;
+
+    let values = match decode_public_values(&public_values) {
+        Ok(DecodedDocument::Gst(values)) => values,
+        Ok(_) => return Err(FixtureError::NotGst),
+        Err(err) => return Err(FixtureError::PublicValues(err)),
+    };
+
+    let checks = [
+        ("gstNumber", values.gst_number == fixture.gst_number),
+        ("legalName", values.legal_name == fixture.legal_name),
+        ("tradeName", values.trade_name == fixture.trade_name),
+        (
+            "registrationTimestamp",
+            values.registration_timestamp == fixture.registration_timestamp,
+        ),
+        (
+            "registrationType",
+            values.registration_type == fixture.registration_type,
+        ),
+        ("signatureValid", values.signature_valid == fixture.signature_valid),
+        (
+            "documentCommitment",
+            hash_matches(
+                "documentCommitment",
+                &fixture.document_commitment,
+                &values.document_commitment.0,
+            )?,
+        ),
+        (
+            "publicKeyHash",
+            hash_matches(
+                "publicKeyHash",
+                &fixture.public_key_hash,
+                &values.public_key_hash.0,
+            )?,
+        ),
+        ("provenAt", values.proven_at == fixture.proven_at),
+    ];
+    match checks.iter().find(|(_, matches)| !matches) {
+        Some((field, _)) => Err(FixtureError::FieldMismatch(field)),
+        None => Ok(()),
+    }
+}
 
 fn main() {
     // Setup the logger.
@@ -101,6 +205,21 @@ fn main() {
     // Parse the command line arguments.
     let args = EVMArgs::parse();
 
+    if let Some(path) = &args.check_fixture {
+        let json = std::fs::read_to_string(path)
+            .unwrap_or_else(|_| panic!("Failed to read fixture from: {}", path.display()));
+        let fixture: SP1GSTProofFixture =
+            serde_json::from_str(&json).expect("fixture is not a GST fixture");
+        match check_gst_fixture_fields(&fixture) {
+            Ok(()) => println!("Fixture is consistent with its public values"),
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     // Setup the prover client.
     let client = ProverClient::from_env();
 
@@ -111,9 +230,36 @@ fn main() {
     let pdf_bytes = std::fs::read(&args.pdf_path)
         .unwrap_or_else(|_| panic!("Failed to read PDF file from: {}", args.pdf_path));
 
+    // Fail before execution rather than inside the program; ZKPDF_MAX_PDF_BYTES overrides
+    let limit = max_pdf_bytes_from_env();
+    assert!(
+        pdf_bytes.len() <= limit,
+        "PDF is {} bytes, above the {} byte limit",
+        pdf_bytes.len(),
+        limit
+    );
+
     // Setup the inputs.
     let mut stdin = SP1Stdin::new();
-    stdin.write(&pdf_bytes);
+    stdin.write(&vec![pdf_bytes.clone()]);
+    // Reveal every GST field and the PAN holder's gender
+    stdin.write(&true);
+    stdin.write(&true);
+    stdin.write(&true);
+    stdin.write(&true);
+    // Zero commitment salt
+    stdin.write(&[0u8; 32]);
+    // Prove the PAN holder is over 18 as of today
+    stdin.write(&vec![18u32]);
+    stdin.write(&Utc::now().format("%Y-%m-%d").to_string());
+    // No expected document commitment
+    stdin.write(&None::<[u8; 32]>);
+    // Commit only the hash of the signer's public key
+    stdin.write(&false);
+    // Commit the verbose GST layout
+    stdin.write(&false);
+    // Proving time, attested by this prover only
+    stdin.write(&(Utc::now().timestamp().max(0) as u64));
 
     println!("PDF Path: {}", args.pdf_path);
     println!("PDF Size: {} bytes", pdf_bytes.len());
@@ -137,73 +283,102 @@ fn create_proof_fixture(
 ) {
     let bytes = proof.public_values.as_slice();
 
-    // Try decoding as GST first
-    if let Ok(GSTValuesStruct {
-        gst_number,
-        legal_name,
-        signature_valid,
-        document_commitment,
-        public_key_hash,
-    }) = GSTValuesStruct::abi_decode(bytes)
-    {
-        let fixture = SP1GSTProofFixture {
+    // The `schema_version` and `doc_kind` header says which struct the program committed
+    match decode_public_values(bytes).expect("failed to decode public values") {
+        DecodedDocument::Gst(GSTValuesStruct {
             gst_number,
             legal_name,
+            trade_name,
+            registration_timestamp,
+            registration_type,
             signature_valid,
-            document_commitment: format!("0x{}", hex::encode(document_commitment.as_ref() as &[u8])),
-            public_key_hash: format!("0x{}", hex::encode(public_key_hash.as_ref() as &[u8])),
-            vkey: vk.bytes32().to_string(),
-            public_values: format!("0x{}", hex::encode(bytes)),
-            proof: format!("0x{}", hex::encode(proof.bytes())),
-        };
-
-        save_fixture(&fixture, system);
-        return;
-    }
+            document_commitment,
+            public_key_hash,
+            proven_at,
+            ..
+        }) => {
+            let fixture = SP1GSTProofFixture {
+                gst_number,
+                legal_name,
+                trade_name,
+                registration_timestamp,
+                registration_type,
+                signature_valid,
+                document_commitment: to_0x(document_commitment.as_ref()),
+                public_key_hash: to_0x(public_key_hash.as_ref()),
+                proven_at,
+                vkey: vk.bytes32().to_string(),
+                public_values: to_0x(bytes),
+                proof: to_0x(&proof.bytes()),
+            };
+            check_gst_fixture_fields(&fixture).expect("generated fixture is inconsistent");
 
-    // If not GST, try decoding as PAN
-    if let Ok(PANValuesStruct {
-        pan_number,
-        legal_name,
-        dob,
-        signature_valid,
-        document_commitment,
-        public_key_hash,
-    }) = PANValuesStruct::abi_decode(bytes)
-    {
-        // commitments
-        let pan_number_commitment =
-            format!("0x{}", hex::encode(keccak256(pan_number.as_bytes())));
-        let holder_name =
-            format!("0x{}", hex::encode(keccak256(legal_name.as_bytes())));
-        let dob_commitment =
-            format!("0x{}", hex::encode(keccak256(dob.as_bytes())));
-
-        // age calculation
-        let dob_parsed = NaiveDate::parse_from_str(&dob, "%Y-%m-%d")
-            .expect("DOB must be in YYYY-MM-DD format");
-        let today = Utc::now().naive_utc().date();
-        let age = today.years_since(dob_parsed).unwrap_or(0);
-        let age_proof_over18 = age >= 18;
-
-        let fixture = SP1PANProofFixture {
+            save_fixture(&fixture, system);
+        }
+        DecodedDocument::Pan(PANValuesStruct {
+            pan_number,
+            legal_name,
+            dob,
+            print_timestamp,
+            age_thresholds,
+            age_over_thresholds,
             signature_valid,
-            document_commitment: format!("0x{}", hex::encode(document_commitment.as_ref() as &[u8])),
-            public_key_hash: format!("0x{}", hex::encode(public_key_hash.as_ref() as &[u8])),
-            vkey: vk.bytes32().to_string(),
-            public_values: format!("0x{}", hex::encode(bytes)),
-            proof: format!("0x{}", hex::encode(proof.bytes())),
-            pan_number_commitment,
-            holder_name,
-            dob_commitment,
-            age_proof_over18,
-        };
-
-        save_fixture(&fixture, system);
-        return;
-    }
+            document_commitment,
+            public_key_hash,
+            proven_at,
+            ..
+        }) => {
+            // commitments
+            let pan_number_commitment =
+                to_0x(keccak256(pan_number.as_bytes()).as_slice());
+            let holder_name =
+                to_0x(keccak256(legal_name.as_bytes()).as_slice());
+            let dob_commitment =
+                to_0x(keccak256(dob.as_bytes()).as_slice());
+
+            // age check proven inside the zkVM
+            let age_proof_over18 = age_thresholds
+                .iter()
+                .position(|threshold| *threshold == 18)
+                .map(|i| age_over_thresholds[i])
+                .unwrap_or(false);
 
-    panic!("Public values could not be decoded as GST or PAN struct!");
+            let fixture = SP1PANProofFixture {
+                signature_valid,
+                document_commitment: to_0x(document_commitment.as_ref()),
+                public_key_hash: to_0x(public_key_hash.as_ref()),
+                vkey: vk.bytes32().to_string(),
+                public_values: to_0x(bytes),
+                proof: to_0x(&proof.bytes()),
+                pan_number_commitment,
+                holder_name,
+                dob_commitment,
+                age_proof_over18,
+                print_timestamp,
+                proven_at,
+            };
+
+            save_fixture(&fixture, system);
+        }
+        DecodedDocument::GstPrivate(_) => {
+            panic!("No EVM fixture is defined for private GST public values")
+        }
+        DecodedDocument::DrivingLicense(_) => {
+            panic!("No EVM fixture is defined for DL public values")
+        }
+        DecodedDocument::Aadhaar(_) => {
+            panic!("No EVM fixture is defined for Aadhaar public values")
+        }
+        DecodedDocument::Bundle(_) => {
+            panic!("No EVM fixture is defined for bundle public values")
+        }
+        DecodedDocument::WithPublicKey(_) => {
+            panic!("No EVM fixture is defined for public values with the full public key")
+        }
+        DecodedDocument::GstPacked(_) => {
+            panic!("No EVM fixture is defined for packed GST public values")
+        }
+    }
 }
 
 /// Helper to save fixture JSON
@@ -218,3 +393,55 @@ fn save_fixture<T: serde::Serialize>(fixture: &T, system: ProofSystem) {
     )
     .expect("failed to write fixture");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolType;
+    use zkpdf_template_lib::{CertificateKind, PUBLIC_VALUES_SCHEMA_VERSION};
+
+    fn fixture() -> SP1GSTProofFixture {
+        let public_values = GSTValuesStruct::abi_encode(&GSTValuesStruct {
+            schema_version: PUBLIC_VALUES_SCHEMA_VERSION,
+            doc_kind: CertificateKind::Gst as u8,
+            gst_number: "27AAPFU0939F1ZV".to_string(),
+            legal_name: "ACME TRADERS".to_string(),
+            trade_name: String::new(),
+            registration_timestamp: 0,
+            registration_type: "Regular".to_string(),
+            signature_valid: true,
+            field_commitment: [3u8; 32].into(),
+            document_commitment: [1u8; 32].into(),
+            committed_match: false,
+            public_key_hash: [2u8; 32].into(),
+            proven_at: 1_700_000_000,
+        });
+
+        SP1GSTProofFixture {
+            gst_number: "27AAPFU0939F1ZV".to_string(),
+            legal_name: "ACME TRADERS".to_string(),
+            trade_name: String::new(),
+            registration_timestamp: 0,
+            registration_type: "Regular".to_string(),
+            signature_valid: true,
+            document_commitment: to_0x(&[1u8; 32]),
+            public_key_hash: to_0x(&[2u8; 32]),
+            proven_at: 1_700_000_000,
+            vkey: String::new(),
+            public_values: to_0x(&public_values),
+            proof: String::new(),
+        }
+    }
+
+    #[test]
+    fn stale_fixture_fields_are_rejected() {
+        assert!(check_gst_fixture_fields(&fixture()).is_ok());
+
+        let mut stale = fixture();
+        stale.document_commitment = to_0x(&[9u8; 32]);
+        assert!(matches!(
+            check_gst_fixture_fields(&stale),
+            Err(FixtureError::FieldMismatch("documentCommitment"))
+        ));
+    }
+}
@@ -13,11 +13,37 @@
 //! ```shell
 //! RUST_LOG=info cargo run --release -- --execute --pdf-path path/to/your/certificate.pdf
 //! ```
+//! Repeating `--pdf-path` proves every PDF together as one bundle:
+//! ```shell
+//! RUST_LOG=info cargo run --release -- --execute --kind 5 --pdf-path pan.pdf --pdf-path gst.pdf
+//! ```
+//! `--pdf-dir` executes every `*.pdf` in a directory and prints one CSV row per file;
+//! files that fail are skipped with a message on stderr:
+//! ```shell
+//! RUST_LOG=info cargo run --release -- --execute --kind 0 --pdf-dir path/to/pdfs > results.csv
+//! ```
+//! `--out path/to/record.json` also writes the decoded public values to a JSON file, whatever
+//! the `--format`.
+//!
+//! `--include-public-key` also commits the signer's full public key, for verifiers that
+//! check it themselves; it makes the public values larger by the size of the key.
+//!
+//! `--packed` commits GST values in the fixed-size `GSTValuesPackedStruct`, which is cheaper
+//! to verify on-chain but carries the legal name only as a commitment.
+//!
+//! Every proof commits `proven_at`, the current time unless `--proof-time` sets it. The
+//! program cannot check the clock, so `proven_at` is only as trustworthy as the prover.
 
-use alloy_sol_types::SolType;
-use clap::Parser;
-use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
-use zkpdf_template_lib::{GSTValuesStruct, PANValuesStruct};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use sp1_sdk::{include_elf, EnvProver, ProverClient, SP1Stdin};
+use zkpdf_template_lib::{
+    decode_public_values,
+    hex_util::{from_0x, to_0x},
+    utils::{gender_from_code, max_pdf_bytes_from_env, GENDER_WITHHELD},
+    DecodedDocument,
+};
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const ZKPDF_TEMPLATE_ELF: &[u8] = include_elf!("zkpdf-template-program");
@@ -35,8 +61,439 @@ struct Args {
     #[arg(long)]
     prove: bool,
 
-    #[arg(long, default_value = "../samples/PAN-card.pdf")]
-    pdf_path: String,
+    /// PDF to prove; repeat to prove several PDFs as one bundle
+    #[arg(long = "pdf-path", default_value = "../samples/PAN-card.pdf")]
+    pdf_paths: Vec<String>,
+
+    /// Execute every `*.pdf` in this directory, printing one CSV row per file
+    #[arg(long, requires = "execute", conflicts_with = "pdf_paths")]
+    pdf_dir: Option<PathBuf>,
+
+    /// Hide the GST number, committing only to its hash
+    #[arg(long)]
+    hide_gst_number: bool,
+
+    /// Hide the legal name, committing only to its hash
+    #[arg(long)]
+    hide_legal_name: bool,
+
+    /// Hide the principal place of business, committing only to its hash
+    #[arg(long)]
+    hide_address: bool,
+
+    /// Hide the PAN holder's gender, committing only to its hash
+    #[arg(long)]
+    hide_gender: bool,
+
+    /// Hex-encoded 32 byte salt mixed into the document commitment (defaults to zero)
+    #[arg(long)]
+    salt: Option<String>,
+
+    /// Comma-separated ages to prove the PAN holder has reached, e.g. `18,21`
+    #[arg(long, value_delimiter = ',', default_value = "18")]
+    age_thresholds: Vec<u32>,
+
+    /// Date (YYYY-MM-DD) the age thresholds are evaluated on (defaults to today)
+    #[arg(long)]
+    as_of: Option<String>,
+
+    /// Hex-encoded document commitment the program must reproduce, or it fails
+    #[arg(long)]
+    expected_commitment: Option<String>,
+
+    /// Commit the signer's full public key, not only its hash (single PDF only)
+    #[arg(long)]
+    include_public_key: bool,
+
+    /// Commit GST values in the fixed-size packed layout, with the name only as a commitment
+    #[arg(long)]
+    packed: bool,
+
+    /// Unix time committed as `proven_at` (defaults to now); attested by this prover only
+    #[arg(long)]
+    proof_time: Option<u64>,
+
+    /// How the decoded public values are printed
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Also write the decoded public values as JSON to this file
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// The single thing the script does for a given `--execute`/`--prove` and `--kind`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Action {
+    ExecuteGst,
+    ExecutePan,
+    ExecuteDl,
+    ExecuteAadhaar,
+    ExecuteBundle,
+    /// `--execute` with a kind the script has no decoded output for.
+    Unsupported(u8),
+    Prove,
+}
+
+impl Args {
+    /// Resolves the requested mode and document kind into exactly one action.
+    fn action(&self) -> Action {
+        match (self.execute, self.kind) {
+            (true, _) if self.pdf_paths.len() > 1 => Action::ExecuteBundle,
+            (true, 0 | 3) => Action::ExecuteGst,
+            (true, 1) => Action::ExecutePan,
+            (true, 2) => Action::ExecuteDl,
+            (true, 4) => Action::ExecuteAadhaar,
+            (true, kind) => Action::Unsupported(kind),
+            (false, _) => Action::Prove,
+        }
+    }
+}
+
+/// Output format for the decoded public values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Decoded GST public values together with the cycle count.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GSTOutput {
+    gst_number: String,
+    legal_name: String,
+    trade_name: String,
+    registration_timestamp: u64,
+    registration_type: String,
+    signature_valid: bool,
+    field_commitment: String,
+    document_commitment: String,
+    committed_match: bool,
+    public_key_hash: String,
+    /// Prover-attested unix time of proving
+    proven_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    cycles: u64,
+}
+
+/// Decoded selective-disclosure GST public values together with the cycle count.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GSTPrivateOutput {
+    gst_number: String,
+    gst_number_commitment: String,
+    legal_name: String,
+    legal_name_commitment: String,
+    signature_valid: bool,
+    field_commitment: String,
+    document_commitment: String,
+    committed_match: bool,
+    public_key_hash: String,
+    /// Prover-attested unix time of proving
+    proven_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    cycles: u64,
+}
+
+/// Decoded packed GST public values together with the cycle count.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GSTPackedOutput {
+    gst_number: String,
+    state_code: u8,
+    legal_name_commitment: String,
+    registration_timestamp: u64,
+    signature_valid: bool,
+    field_commitment: String,
+    document_commitment: String,
+    committed_match: bool,
+    public_key_hash: String,
+    /// Prover-attested unix time of proving
+    proven_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    cycles: u64,
+}
+
+/// Decoded PAN public values together with the cycle count.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PANOutput {
+    pan_number: String,
+    legal_name: String,
+    dob: String,
+    print_timestamp: u64,
+    gender: Option<String>,
+    age_thresholds: Vec<u32>,
+    age_over_thresholds: Vec<bool>,
+    age_as_of: String,
+    signature_valid: bool,
+    field_commitment: String,
+    document_commitment: String,
+    committed_match: bool,
+    public_key_hash: String,
+    /// Prover-attested unix time of proving
+    proven_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    cycles: u64,
+}
+
+/// Decoded Driving License public values together with the cycle count.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DLOutput {
+    dl_number: String,
+    holder_name: String,
+    issuing_authority: String,
+    license_type: String,
+    expiry_date: String,
+    signature_valid: bool,
+    field_commitment: String,
+    document_commitment: String,
+    committed_match: bool,
+    public_key_hash: String,
+    /// Prover-attested unix time of proving
+    proven_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    cycles: u64,
+}
+
+/// Decoded e-Aadhaar public values together with the cycle count.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AadhaarOutput {
+    masked_uid_last4: String,
+    name: String,
+    dob: String,
+    gender: Option<String>,
+    signature_valid: bool,
+    field_commitment: String,
+    document_commitment: String,
+    committed_match: bool,
+    public_key_hash: String,
+    /// Prover-attested unix time of proving
+    proven_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    cycles: u64,
+}
+
+/// Decoded public values of a PDF bundle together with the cycle count.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleOutput {
+    doc_kinds: Vec<u8>,
+    field_commitments: Vec<String>,
+    document_commitments: Vec<String>,
+    public_key_hashes: Vec<String>,
+    all_signatures_valid: bool,
+    /// Prover-attested unix time of proving
+    proven_at: u64,
+    cycles: u64,
+}
+
+/// Hex-encodes each `bytes32` value of an array with a `0x` prefix.
+fn to_hex_each<T: AsRef<[u8]>>(values: &[T]) -> Vec<String> {
+    values.iter().map(|value| to_0x(value.as_ref())).collect()
+}
+
+/// Unwraps values committed with `--include-public-key` into the document's own public
+/// values and the hex-encoded key; other values are returned as they are.
+fn split_public_key(public_values: &[u8]) -> (Vec<u8>, Option<String>) {
+    match decode_public_values(public_values) {
+        Ok(DecodedDocument::WithPublicKey(wrapped)) => {
+            (wrapped.values.to_vec(), Some(to_0x(&wrapped.public_key)))
+        }
+        _ => (public_values.to_vec(), None),
+    }
+}
+
+/// Decodes the program's public values, exiting with a message rather than panicking when
+/// they are malformed or were committed by a different program version.
+fn decode_or_exit(public_values: &[u8]) -> DecodedDocument {
+    DecodedDocument::try_from(public_values).unwrap_or_else(|err| {
+        eprintln!("Could not decode the public values as any known document: {}", err);
+        std::process::exit(1);
+    })
+}
+
+/// Prints a decoded output as a single JSON object on stdout.
+fn print_json<T: Serialize>(output: &T) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(output).expect("failed to serialize output")
+    );
+}
+
+/// Writes a decoded output as JSON to `path`, creating its parent directories.
+fn write_json<T: Serialize>(output: &T, path: &Path) {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).expect("failed to create output directory");
+    }
+    std::fs::write(
+        path,
+        serde_json::to_string_pretty(output).expect("failed to serialize output"),
+    )
+    .unwrap_or_else(|_| panic!("Failed to write output to: {}", path.display()));
+}
+
+/// The program inputs for one run over `pdfs`.
+fn program_stdin(
+    args: &Args,
+    pdfs: &[Vec<u8>],
+    salt: [u8; 32],
+    expected_commitment: Option<[u8; 32]>,
+) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&pdfs);
+    stdin.write(&!args.hide_gst_number);
+    stdin.write(&!args.hide_legal_name);
+    stdin.write(&!args.hide_address);
+    stdin.write(&!args.hide_gender);
+    stdin.write(&salt);
+    stdin.write(&args.age_thresholds);
+    stdin.write(
+        &args
+            .as_of
+            .clone()
+            .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string()),
+    );
+    stdin.write(&expected_commitment);
+    stdin.write(&args.include_public_key);
+    stdin.write(&args.packed);
+    stdin.write(
+        &args
+            .proof_time
+            .unwrap_or_else(|| chrono::Utc::now().timestamp().max(0) as u64),
+    );
+    stdin
+}
+
+/// Header of the `--pdf-dir` CSV output.
+const CSV_HEADER: &str = "file,doc_type,identifier,name,signature_valid,cycles";
+
+/// Document type, identifier, name and signature validity of decoded public values, for
+/// one `--pdf-dir` CSV row; hidden or committed-only fields are left empty.
+fn csv_summary(decoded: &DecodedDocument) -> Option<(&'static str, String, String, bool)> {
+    Some(match decoded {
+        DecodedDocument::Gst(values) => (
+            "gst",
+            values.gst_number.clone(),
+            values.legal_name.clone(),
+            values.signature_valid,
+        ),
+        DecodedDocument::GstPrivate(values) => (
+            "gst",
+            values.gst_number.clone(),
+            values.legal_name.clone(),
+            values.signature_valid,
+        ),
+        DecodedDocument::GstPacked(values) => {
+            ("gst", values.gstin(), String::new(), values.signature_valid)
+        }
+        DecodedDocument::Pan(values) => (
+            "pan",
+            values.pan_number.clone(),
+            values.legal_name.clone(),
+            values.signature_valid,
+        ),
+        DecodedDocument::DrivingLicense(values) => (
+            "driving_license",
+            values.dl_number.clone(),
+            values.holder_name.clone(),
+            values.signature_valid,
+        ),
+        DecodedDocument::Aadhaar(values) => (
+            "aadhaar",
+            values.masked_uid_last4.clone(),
+            values.name.clone(),
+            values.signature_valid,
+        ),
+        DecodedDocument::Bundle(_) | DecodedDocument::WithPublicKey(_) => return None,
+    })
+}
+
+/// Quotes a CSV field when it holds a comma, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Executes the program over every `*.pdf` in `dir`, in file name order, printing one CSV
+/// row per file. Files that can't be read, executed or decoded are skipped.
+fn execute_dir(
+    client: &EnvProver,
+    args: &Args,
+    dir: &Path,
+    salt: [u8; 32],
+    expected_commitment: Option<[u8; 32]>,
+) {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap_or_else(|_| panic!("Failed to read PDF directory: {}", dir.display()))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"))
+        })
+        .collect();
+    paths.sort();
+
+    let limit = max_pdf_bytes_from_env();
+    println!("{}", CSV_HEADER);
+    for path in paths {
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        let pdf_bytes = match std::fs::read(&path) {
+            Ok(pdf_bytes) => pdf_bytes,
+            Err(err) => {
+                eprintln!("Skipping {}: {}", file, err);
+                continue;
+            }
+        };
+        if pdf_bytes.len() > limit {
+            eprintln!(
+                "Skipping {}: {} bytes, above the {} byte limit",
+                file,
+                pdf_bytes.len(),
+                limit
+            );
+            continue;
+        }
+
+        let stdin = program_stdin(args, &[pdf_bytes], salt, expected_commitment);
+        let (output, report) = match client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run() {
+            Ok(executed) => executed,
+            Err(err) => {
+                eprintln!("Skipping {}: execution failed: {}", file, err);
+                continue;
+            }
+        };
+        let (public_values, _) = split_public_key(output.as_slice());
+        let summary = DecodedDocument::try_from(public_values.as_slice())
+            .ok()
+            .as_ref()
+            .and_then(csv_summary);
+        let Some((doc_type, identifier, name, signature_valid)) = summary else {
+            eprintln!("Skipping {}: public values are not a single document", file);
+            continue;
+        };
+        println!(
+            "{},{},{},{},{},{}",
+            csv_field(&file),
+            doc_type,
+            csv_field(&identifier),
+            csv_field(&name),
+            signature_valid,
+            report.total_instruction_count()
+        );
+    }
 }
 
 fn main() {
@@ -55,106 +512,493 @@ fn main() {
     // Setup the prover client.
     let client = ProverClient::from_env();
 
+    let salt: [u8; 32] = match &args.salt {
+        Some(salt) => from_0x(salt)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .unwrap_or_else(|| panic!("Salt must be 32 hex-encoded bytes: {}", salt)),
+        None => [0u8; 32],
+    };
+
+    let expected_commitment: Option<[u8; 32]> =
+        args.expected_commitment.as_ref().map(|commitment| {
+            from_0x(commitment)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Expected commitment must be 32 hex-encoded bytes: {}",
+                        commitment
+                    )
+                })
+        });
+
+    if let Some(pdf_dir) = &args.pdf_dir {
+        execute_dir(&client, &args, pdf_dir, salt, expected_commitment);
+        return;
+    }
+
     // Read PDF bytes from file
-    let pdf_bytes = std::fs::read(&args.pdf_path)
-        .unwrap_or_else(|_| panic!("Failed to read PDF file from: {}", args.pdf_path));
+    let pdfs: Vec<Vec<u8>> = args
+        .pdf_paths
+        .iter()
+        .map(|pdf_path| {
+            std::fs::read(pdf_path)
+                .unwrap_or_else(|_| panic!("Failed to read PDF file from: {}", pdf_path))
+        })
+        .collect();
 
-    // Setup the inputs.
-    let mut stdin = SP1Stdin::new();
-    stdin.write(&pdf_bytes);
-
-    println!("PDF Path: {}", args.pdf_path);
-    println!("PDF Size: {} bytes", pdf_bytes.len());
-
-    // GST Certificate
-    if args.execute && args.kind == 0 {
-        // Execute the program
-        let (output, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
-        println!("Program executed successfully.");
-
-        // Read the output.
-        let decoded = GSTValuesStruct::abi_decode(output.as_slice()).unwrap();
-        let GSTValuesStruct {
-            gst_number,
-            legal_name,
-            signature_valid,
-            document_commitment,
-            public_key_hash,
-        } = decoded;
-        println!("GST Number: {}", gst_number);
-        println!("Legal Name: {}", legal_name);
-        println!("Signature Valid: {}", signature_valid);
-        println!(
-            "Document Commitment: 0x{}",
-            hex::encode(document_commitment.as_ref() as &[u8])
-        );
-        println!(
-            "Public Key Hash: 0x{}",
-            hex::encode(public_key_hash.as_ref() as &[u8])
+    // Fail before execution rather than inside the program; ZKPDF_MAX_PDF_BYTES overrides
+    let limit = max_pdf_bytes_from_env();
+    for pdf_bytes in &pdfs {
+        assert!(
+            pdf_bytes.len() <= limit,
+            "PDF is {} bytes, above the {} byte limit",
+            pdf_bytes.len(),
+            limit
         );
+    }
 
-        // Record the number of cycles executed.
-        println!("Number of cycles: {}", report.total_instruction_count());
-    } else {
-        // Setup the program for proving.
-        let (pk, vk) = client.setup(ZKPDF_TEMPLATE_ELF);
+    // Setup the inputs.
+    let stdin = program_stdin(&args, &pdfs, salt, expected_commitment);
+
+    // Keep stdout a single JSON document when `--format json` is selected.
+    let text = args.format == OutputFormat::Text;
+    if text {
+        for (pdf_path, pdf_bytes) in args.pdf_paths.iter().zip(&pdfs) {
+            println!("PDF Path: {}", pdf_path);
+            println!("PDF Size: {} bytes", pdf_bytes.len());
+        }
+    }
+
+    match args.action() {
+        // GST Certificate
+        Action::ExecuteGst => {
+            // Execute the program
+            let (output, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
+            if text {
+                println!("Program executed successfully.");
+            }
+
+            // Read the output.
+            let (public_values, public_key) = split_public_key(output.as_slice());
+            match decode_or_exit(&public_values) {
+                DecodedDocument::GstPrivate(decoded) => {
+                    let output = GSTPrivateOutput {
+                        gst_number: decoded.gst_number,
+                        gst_number_commitment: to_0x(decoded.gst_number_commitment.as_ref()),
+                        legal_name: decoded.legal_name,
+                        legal_name_commitment: to_0x(decoded.legal_name_commitment.as_ref()),
+                        signature_valid: decoded.signature_valid,
+                        field_commitment: to_0x(decoded.field_commitment.as_ref()),
+                        document_commitment: to_0x(decoded.document_commitment.as_ref()),
+                        committed_match: decoded.committed_match,
+                        public_key_hash: to_0x(decoded.public_key_hash.as_ref()),
+                        proven_at: decoded.proven_at,
+                        public_key: public_key.clone(),
+                        cycles: report.total_instruction_count(),
+                    };
+                    if text {
+                        println!("GST Number: {}", output.gst_number);
+                        println!("GST Number Commitment: {}", output.gst_number_commitment);
+                        println!("Legal Name: {}", output.legal_name);
+                        println!("Legal Name Commitment: {}", output.legal_name_commitment);
+                        println!("Signature Valid: {}", output.signature_valid);
+                        println!("Field Commitment: {}", output.field_commitment);
+                        println!("Document Commitment: {}", output.document_commitment);
+                        println!("Committed Match: {}", output.committed_match);
+                        println!("Public Key Hash: {}", output.public_key_hash);
+                        println!("Proven At: {}", output.proven_at);
+                        if let Some(public_key) = &output.public_key {
+                            println!("Public Key: {}", public_key);
+                        }
+                        println!("Number of cycles: {}", output.cycles);
+                    } else {
+                        print_json(&output);
+                    }
+                    if let Some(out) = &args.out {
+                        write_json(&output, out);
+                    }
+                }
+                DecodedDocument::Gst(decoded) => {
+                    let output = GSTOutput {
+                        gst_number: decoded.gst_number,
+                        legal_name: decoded.legal_name,
+                        trade_name: decoded.trade_name,
+                        registration_timestamp: decoded.registration_timestamp,
+                        registration_type: decoded.registration_type,
+                        signature_valid: decoded.signature_valid,
+                        field_commitment: to_0x(decoded.field_commitment.as_ref()),
+                        document_commitment: to_0x(decoded.document_commitment.as_ref()),
+                        committed_match: decoded.committed_match,
+                        public_key_hash: to_0x(decoded.public_key_hash.as_ref()),
+                        proven_at: decoded.proven_at,
+                        public_key: public_key.clone(),
+                        cycles: report.total_instruction_count(),
+                    };
+                    if text {
+                        println!("GST Number: {}", output.gst_number);
+                        println!("Legal Name: {}", output.legal_name);
+                        println!("Trade Name: {}", output.trade_name);
+                        println!("Registration Timestamp: {}", output.registration_timestamp);
+                        println!("Registration Type: {}", output.registration_type);
+                        println!("Signature Valid: {}", output.signature_valid);
+                        println!("Field Commitment: {}", output.field_commitment);
+                        println!("Document Commitment: {}", output.document_commitment);
+                        println!("Committed Match: {}", output.committed_match);
+                        println!("Public Key Hash: {}", output.public_key_hash);
+                        println!("Proven At: {}", output.proven_at);
+                        if let Some(public_key) = &output.public_key {
+                            println!("Public Key: {}", public_key);
+                        }
+                        println!("Number of cycles: {}", output.cycles);
+                    } else {
+                        print_json(&output);
+                    }
+                    if let Some(out) = &args.out {
+                        write_json(&output, out);
+                    }
+                }
+                DecodedDocument::GstPacked(decoded) => {
+                    let output = GSTPackedOutput {
+                        gst_number: decoded.gstin(),
+                        state_code: decoded.state_code,
+                        legal_name_commitment: to_0x(decoded.legal_name_commitment.as_ref()),
+                        registration_timestamp: decoded.registration_timestamp,
+                        signature_valid: decoded.signature_valid,
+                        field_commitment: to_0x(decoded.field_commitment.as_ref()),
+                        document_commitment: to_0x(decoded.document_commitment.as_ref()),
+                        committed_match: decoded.committed_match,
+                        public_key_hash: to_0x(decoded.public_key_hash.as_ref()),
+                        proven_at: decoded.proven_at,
+                        public_key: public_key.clone(),
+                        cycles: report.total_instruction_count(),
+                    };
+                    if text {
+                        println!("GST Number: {}", output.gst_number);
+                        println!("State Code: {}", output.state_code);
+                        println!("Legal Name Commitment: {}", output.legal_name_commitment);
+                        println!("Registration Timestamp: {}", output.registration_timestamp);
+                        println!("Signature Valid: {}", output.signature_valid);
+                        println!("Field Commitment: {}", output.field_commitment);
+                        println!("Document Commitment: {}", output.document_commitment);
+                        println!("Committed Match: {}", output.committed_match);
+                        println!("Public Key Hash: {}", output.public_key_hash);
+                        println!("Proven At: {}", output.proven_at);
+                        if let Some(public_key) = &output.public_key {
+                            println!("Public Key: {}", public_key);
+                        }
+                        println!("Number of cycles: {}", output.cycles);
+                    } else {
+                        print_json(&output);
+                    }
+                    if let Some(out) = &args.out {
+                        write_json(&output, out);
+                    }
+                }
+                _ => panic!("Program did not commit GST public values"),
+            }
+        }
+        // PAN Card
+        Action::ExecutePan => {
+            // Execute the program
+            let (output, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
+            if text {
+                println!("Program executed successfully.");
+            }
 
-        // Generate the proof
-        let proof = client
-            .prove(&pk, &stdin)
-            .run()
-            .expect("failed to generate proof");
+            // Read the output.
+            let (public_values, public_key) = split_public_key(output.as_slice());
+            let DecodedDocument::Pan(decoded) = decode_or_exit(&public_values) else {
+                panic!("Program did not commit PAN public values");
+            };
+            let output = PANOutput {
+                pan_number: decoded.pan_number,
+                legal_name: decoded.legal_name,
+                dob: decoded.dob,
+                print_timestamp: decoded.print_timestamp,
+                gender: match decoded.gender {
+                    0 => None,
+                    GENDER_WITHHELD => Some("Withheld".to_string()),
+                    code => gender_from_code(code).map(|gender| format!("{:?}", gender)),
+                },
+                age_thresholds: decoded.age_thresholds,
+                age_over_thresholds: decoded.age_over_thresholds,
+                age_as_of: decoded.age_as_of,
+                signature_valid: decoded.signature_valid,
+                field_commitment: to_0x(decoded.field_commitment.as_ref()),
+                document_commitment: to_0x(decoded.document_commitment.as_ref()),
+                committed_match: decoded.committed_match,
+                public_key_hash: to_0x(decoded.public_key_hash.as_ref()),
+                proven_at: decoded.proven_at,
+                public_key,
+                cycles: report.total_instruction_count(),
+            };
+            if text {
+                println!("PAN Number: {}", output.pan_number);
+                println!("Legal Name: {}", output.legal_name);
+                println!("DOB: {}", output.dob);
+                if output.print_timestamp != 0 {
+                    println!("Print Timestamp: {}", output.print_timestamp);
+                }
+                if let Some(gender) = &output.gender {
+                    println!("Gender: {}", gender);
+                }
+                let thresholds = output.age_thresholds.iter();
+                for (threshold, over) in thresholds.zip(&output.age_over_thresholds) {
+                    println!("Age Over {} (as of {}): {}", threshold, output.age_as_of, over);
+                }
+                println!("Signature Valid: {}", output.signature_valid);
+                println!("Field Commitment: {}", output.field_commitment);
+                println!("Document Commitment: {}", output.document_commitment);
+                println!("Committed Match: {}", output.committed_match);
+                println!("Public Key Hash: {}", output.public_key_hash);
+                println!("Proven At: {}", output.proven_at);
+                if let Some(public_key) = &output.public_key {
+                    println!("Public Key: {}", public_key);
+                }
+                println!("Number of cycles: {}", output.cycles);
+            } else {
+                print_json(&output);
+            }
+            if let Some(out) = &args.out {
+                write_json(&output, out);
+            }
+        }
+        // Driving License
+        Action::ExecuteDl => {
+            // Execute the program
+            let (output, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
+            if text {
+                println!("Program executed successfully.");
+            }
 
-        println!("Successfully generated proof!");
+            // Read the output.
+            let (public_values, public_key) = split_public_key(output.as_slice());
+            let DecodedDocument::DrivingLicense(decoded) = decode_or_exit(&public_values) else {
+                panic!("Program did not commit Driving License public values");
+            };
+            let output = DLOutput {
+                dl_number: decoded.dl_number,
+                holder_name: decoded.holder_name,
+                issuing_authority: decoded.issuing_authority,
+                license_type: decoded.license_type,
+                expiry_date: decoded.expiry_date,
+                signature_valid: decoded.signature_valid,
+                field_commitment: to_0x(decoded.field_commitment.as_ref()),
+                document_commitment: to_0x(decoded.document_commitment.as_ref()),
+                committed_match: decoded.committed_match,
+                public_key_hash: to_0x(decoded.public_key_hash.as_ref()),
+                proven_at: decoded.proven_at,
+                public_key,
+                cycles: report.total_instruction_count(),
+            };
+            if text {
+                println!("DL Number: {}", output.dl_number);
+                println!("Holder Name: {}", output.holder_name);
+                println!("Issuing Authority: {}", output.issuing_authority);
+                println!("License Type: {}", output.license_type);
+                println!("Expiry Date: {}", output.expiry_date);
+                println!("Signature Valid: {}", output.signature_valid);
+                println!("Field Commitment: {}", output.field_commitment);
+                println!("Document Commitment: {}", output.document_commitment);
+                println!("Committed Match: {}", output.committed_match);
+                println!("Public Key Hash: {}", output.public_key_hash);
+                println!("Proven At: {}", output.proven_at);
+                if let Some(public_key) = &output.public_key {
+                    println!("Public Key: {}", public_key);
+                }
+                println!("Number of cycles: {}", output.cycles);
+            } else {
+                print_json(&output);
+            }
+            if let Some(out) = &args.out {
+                write_json(&output, out);
+            }
+        }
+        // e-Aadhaar; the program only accepts it when built with the `aadhaar` feature
+        Action::ExecuteAadhaar => {
+            // Execute the program
+            let (output, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
+            if text {
+                println!("Program executed successfully.");
+            }
 
-        // Verify the proof.
-        client.verify(&proof, &vk).expect("failed to verify proof");
-        println!("Successfully verified proof!");
+            // Read the output.
+            let (public_values, public_key) = split_public_key(output.as_slice());
+            let DecodedDocument::Aadhaar(decoded) = decode_or_exit(&public_values) else {
+                panic!("Program did not commit Aadhaar public values");
+            };
+            let output = AadhaarOutput {
+                masked_uid_last4: decoded.masked_uid_last4,
+                name: decoded.name,
+                dob: decoded.dob,
+                gender: gender_from_code(decoded.gender).map(|gender| format!("{:?}", gender)),
+                signature_valid: decoded.signature_valid,
+                field_commitment: to_0x(decoded.field_commitment.as_ref()),
+                document_commitment: to_0x(decoded.document_commitment.as_ref()),
+                committed_match: decoded.committed_match,
+                public_key_hash: to_0x(decoded.public_key_hash.as_ref()),
+                proven_at: decoded.proven_at,
+                public_key,
+                cycles: report.total_instruction_count(),
+            };
+            if text {
+                println!("Aadhaar Number: XXXX XXXX {}", output.masked_uid_last4);
+                println!("Name: {}", output.name);
+                println!("DOB: {}", output.dob);
+                if let Some(gender) = &output.gender {
+                    println!("Gender: {}", gender);
+                }
+                println!("Signature Valid: {}", output.signature_valid);
+                println!("Field Commitment: {}", output.field_commitment);
+                println!("Document Commitment: {}", output.document_commitment);
+                println!("Committed Match: {}", output.committed_match);
+                println!("Public Key Hash: {}", output.public_key_hash);
+                println!("Proven At: {}", output.proven_at);
+                if let Some(public_key) = &output.public_key {
+                    println!("Public Key: {}", public_key);
+                }
+                println!("Number of cycles: {}", output.cycles);
+            } else {
+                print_json(&output);
+            }
+            if let Some(out) = &args.out {
+                write_json(&output, out);
+            }
+        }
+        // Several PDFs proven together
+        Action::ExecuteBundle => {
+            // Execute the program
+            let (output, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
+            if text {
+                println!("Program executed successfully.");
+            }
+
+            // Read the output.
+            let DecodedDocument::Bundle(decoded) = decode_or_exit(output.as_slice()) else {
+                panic!("Program did not commit bundle public values");
+            };
+            let output = BundleOutput {
+                doc_kinds: decoded.doc_kinds,
+                field_commitments: to_hex_each(&decoded.field_commitments),
+                document_commitments: to_hex_each(&decoded.document_commitments),
+                public_key_hashes: to_hex_each(&decoded.public_key_hashes),
+                all_signatures_valid: decoded.all_signatures_valid,
+                proven_at: decoded.proven_at,
+                cycles: report.total_instruction_count(),
+            };
+            if text {
+                for (i, doc_kind) in output.doc_kinds.iter().enumerate() {
+                    println!("Document {} Kind: {}", i, doc_kind);
+                    println!("Document {} Field Commitment: {}", i, output.field_commitments[i]);
+                    println!(
+                        "Document {} Document Commitment: {}",
+                        i, output.document_commitments[i]
+                    );
+                    println!("Document {} Public Key Hash: {}", i, output.public_key_hashes[i]);
+                }
+                println!("All Signatures Valid: {}", output.all_signatures_valid);
+                println!("Proven At: {}", output.proven_at);
+                println!("Number of cycles: {}", output.cycles);
+            } else {
+                print_json(&output);
+            }
+            if let Some(out) = &args.out {
+                write_json(&output, out);
+            }
+        }
+        Action::Unsupported(kind) => {
+            eprintln!(
+                "Error: --execute has no output for --kind {}; use 0/3 (GST), 1 (PAN), 2 (DL), \
+                 4 (Aadhaar), or repeat --pdf-path for a bundle",
+                kind
+            );
+            std::process::exit(1);
+        }
+        Action::Prove => {
+            // Setup the program for proving.
+            let (pk, vk) = client.setup(ZKPDF_TEMPLATE_ELF);
+
+            // Generate the proof
+            let proof = client
+                .prove(&pk, &stdin)
+                .run()
+                .expect("failed to generate proof");
+
+            println!("Successfully generated proof!");
+
+            // Verify the proof.
+            client.verify(&proof, &vk).expect("failed to verify proof");
+            println!("Successfully verified proof!");
+        }
     }
+}
 
-    // PAN Card
-    if args.execute && args.kind == 1 {
-        // Execute the program
-        let (output, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
-        println!("Program executed successfully.");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Read the output.
-        let decoded = PANValuesStruct::abi_decode(output.as_slice()).unwrap();
-        let PANValuesStruct {
-            signature_valid,
-            document_commitment,
-            public_key_hash,
-            pan_number,
-            legal_name,
-            dob,
-        } = decoded;
-        println!("PAN Number: {}", pan_number);
-        println!("Signature Valid: {}", signature_valid);
-        println!(
-            "Document Commitment: 0x{}",
-            hex::encode(document_commitment.as_ref() as &[u8])
-        );
-        println!(
-            "Public Key Hash: 0x{}",
-            hex::encode(public_key_hash.as_ref() as &[u8])
-        );
+    fn action_for(args: &[&str]) -> Action {
+        Args::try_parse_from(std::iter::once("zkpdf-template").chain(args.iter().copied()))
+            .unwrap()
+            .action()
+    }
 
-        // Record the number of cycles executed.
-        println!("Number of cycles: {}", report.total_instruction_count());
-    } else {
-        // Setup the program for proving.
-        let (pk, vk) = client.setup(ZKPDF_TEMPLATE_ELF);
+    #[test]
+    fn execute_never_proves() {
+        assert_eq!(action_for(&["--execute", "--kind", "0"]), Action::ExecuteGst);
+        assert_eq!(action_for(&["--execute", "--kind", "1"]), Action::ExecutePan);
+        assert_eq!(action_for(&["--execute", "--kind", "2"]), Action::ExecuteDl);
+        assert_eq!(action_for(&["--execute", "--kind", "3"]), Action::ExecuteGst);
+        assert_eq!(action_for(&["--execute", "--kind", "4"]), Action::ExecuteAadhaar);
+    }
+
+    #[test]
+    fn unhandled_kinds_are_rejected() {
+        assert_eq!(action_for(&["--execute", "--kind", "5"]), Action::Unsupported(5));
+    }
 
-        // Generate the proof
-        let proof = client
-        .prove(&pk, &stdin)
-        .run()
-        .expect("failed to generate proof");
+    #[test]
+    fn several_pdfs_execute_as_bundle() {
+        let args = ["--execute", "--kind", "0", "--pdf-path", "a.pdf", "--pdf-path", "b.pdf"];
+        assert_eq!(action_for(&args), Action::ExecuteBundle);
+    }
+
+    #[test]
+    fn prove_never_executes() {
+        for kind in ["0", "1", "2", "3", "4", "5"] {
+            assert_eq!(action_for(&["--prove", "--kind", kind]), Action::Prove);
+        }
+    }
+
+    #[test]
+    fn pdf_dir_only_executes() {
+        let parse = |args: &[&str]| {
+            Args::try_parse_from(std::iter::once("zkpdf-template").chain(args.iter().copied()))
+        };
+        assert!(parse(&["--execute", "--kind", "0", "--pdf-dir", "pdfs"]).is_ok());
+        assert!(parse(&["--prove", "--kind", "0", "--pdf-dir", "pdfs"]).is_err());
+        assert!(parse(&["--execute", "--kind", "0", "--pdf-dir", "pdfs", "--pdf-path", "a.pdf"])
+            .is_err());
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("ACME TRADERS"), "ACME TRADERS");
+        assert_eq!(csv_field("ACME, INC"), "\"ACME, INC\"");
+        assert_eq!(csv_field("THE \"ACME\" CO"), "\"THE \"\"ACME\"\" CO\"");
+    }
 
-        println!("Successfully generated proof!");
+    #[test]
+    fn out_creates_parent_directories() {
+        let dir = std::env::temp_dir().join(format!("zkpdf-out-{}", std::process::id()));
+        let path = dir.join("records").join("gst.json");
+        write_json(&serde_json::json!({ "cycles": 1 }), &path);
 
-        // Verify the proof.
-        client.verify(&proof, &vk).expect("failed to verify proof");
-        println!("Successfully verified proof!");
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["cycles"], 1);
+        std::fs::remove_dir_all(dir).unwrap();
     }
 }
@@ -1,10 +1,53 @@
-use sp1_sdk::{include_elf, HashableKey, Prover, ProverClient};
+//! Prints the program's verification key.
+//!
+//! With `--json`, also prints the keccak256 hash of the program ELF and the SP1 circuit
+//! version, tying an on-chain vkey to the exact program build:
+//! ```shell
+//! cargo run --release --bin vkey -- --json
+//! ```
+
+use alloy_primitives::keccak256;
+use clap::Parser;
+use serde::Serialize;
+use sp1_sdk::{include_elf, HashableKey, Prover, ProverClient, SP1_CIRCUIT_VERSION};
+use zkpdf_template_lib::hex_util::to_0x;
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const ZKPDF_TEMPLATE_ELF: &[u8] = include_elf!("zkpdf-template-program");
 
+/// The arguments for the vkey command.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct VkeyArgs {
+    /// Print the vkey, program hash and SP1 version as a JSON object
+    #[arg(long)]
+    json: bool,
+}
+
+/// A record of which program build a vkey was derived from.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VkeyRecord {
+    vkey: String,
+    program_hash: String,
+    sp1_version: String,
+}
+
 fn main() {
+    let args = VkeyArgs::parse();
+
     let prover = ProverClient::builder().cpu().build();
     let (_, vk) = prover.setup(ZKPDF_TEMPLATE_ELF);
-    println!("{}", vk.bytes32());
+
+    if !args.json {
+        println!("{}", vk.bytes32());
+        return;
+    }
+
+    let record = VkeyRecord {
+        vkey: vk.bytes32(),
+        program_hash: to_0x(keccak256(ZKPDF_TEMPLATE_ELF).as_slice()),
+        sp1_version: SP1_CIRCUIT_VERSION.to_string(),
+    };
+    println!("{}", serde_json::to_string_pretty(&record).unwrap());
 }
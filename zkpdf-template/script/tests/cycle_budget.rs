@@ -0,0 +1,86 @@
+//! Fails when executing the program on the sample PDF takes more than `tolerance_percent`
+//! over the cycle count committed in `cycle-baseline.json`.
+//!
+//! The budget is measured on the GST sample, standing in for a PAN card, since no PAN
+//! sample PDF ships in `samples/`.
+//!
+//! Executing the guest takes a while, so the test is ignored by default:
+//! ```shell
+//! cd script
+//! cargo test --release --test cycle_budget -- --ignored
+//! ```
+//! To record the baseline, first or after an intentional change in cost, run it with
+//! `UPDATE_CYCLE_BASELINE=1` and commit the rewritten `cycle-baseline.json`.
+
+use serde::Deserialize;
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+
+const ZKPDF_TEMPLATE_ELF: &[u8] = include_elf!("zkpdf-template-program");
+
+const BASELINE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/cycle-baseline.json");
+
+/// Committed cycle budget for one sample PDF.
+#[derive(Deserialize)]
+struct CycleBaseline {
+    pdf: String,
+    as_of: String,
+    total_instruction_count: Option<u64>,
+    tolerance_percent: u64,
+}
+
+#[test]
+#[ignore = "executes the zkVM program"]
+fn sample_stays_within_cycle_budget() {
+    let baseline_json =
+        std::fs::read_to_string(BASELINE_PATH).expect("Failed to read cycle-baseline.json");
+    let baseline: CycleBaseline =
+        serde_json::from_str(&baseline_json).expect("cycle-baseline.json is malformed");
+
+    let pdf_path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), baseline.pdf);
+    let pdf_bytes = std::fs::read(&pdf_path)
+        .unwrap_or_else(|_| panic!("Failed to read PDF file from: {}", pdf_path));
+
+    // Same inputs as `--execute` with the defaults, except a fixed date so the age
+    // computation cannot change the count.
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&vec![pdf_bytes]);
+    for reveal in [true; 4] {
+        stdin.write(&reveal);
+    }
+    stdin.write(&[0u8; 32]);
+    stdin.write(&Vec::<u32>::new());
+    stdin.write(&baseline.as_of);
+    stdin.write(&None::<[u8; 32]>);
+    stdin.write(&false);
+    stdin.write(&false);
+    stdin.write(&0u64);
+
+    let client = ProverClient::from_env();
+    let (_, report) = client.execute(ZKPDF_TEMPLATE_ELF, &stdin).run().unwrap();
+    let cycles = report.total_instruction_count();
+    println!("{}: {} cycles", baseline.pdf, cycles);
+
+    if std::env::var_os("UPDATE_CYCLE_BASELINE").is_some() {
+        let mut json: serde_json::Value = serde_json::from_str(&baseline_json).unwrap();
+        json["total_instruction_count"] = cycles.into();
+        let json = serde_json::to_string_pretty(&json).unwrap();
+        std::fs::write(BASELINE_PATH, json + "\n").expect("Failed to write cycle-baseline.json");
+        return;
+    }
+
+    let Some(budget) = baseline.total_instruction_count else {
+        panic!(
+            "No cycle baseline recorded; rerun with UPDATE_CYCLE_BASELINE=1 to record {}",
+            cycles
+        );
+    };
+    let limit = budget + budget * baseline.tolerance_percent / 100;
+    assert!(
+        cycles <= limit,
+        "{} cycles is more than {}% over the baseline of {}; if the increase is intended, \
+         update cycle-baseline.json",
+        cycles,
+        baseline.tolerance_percent,
+        budget
+    );
+}